@@ -0,0 +1,204 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ui::{util::str::Spaceable, view::table::ingredient::IngredientData};
+use strum_macros::{Display, EnumIter};
+
+#[derive(Clone, Copy, Debug, Display, EnumIter, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Markdown,
+    Text,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Text => "txt",
+        }
+    }
+
+    pub fn export(&self, units: f32, data: &[IngredientData]) -> String {
+        match self {
+            ExportFormat::Csv => to_csv(units, data),
+            ExportFormat::Markdown => to_markdown(units, data),
+            ExportFormat::Text => to_text(units, data),
+        }
+    }
+}
+
+/// Build the header and rows, only including the label and notes columns when an
+/// ingredient makes use of them
+fn table(data: &[IngredientData], suffixes: bool) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let (percent, grams) = if suffixes { ("%", "g") } else { ("", "") };
+    let label = data.iter().any(|item| !item.label.trim().is_empty());
+    let notes = data.iter().any(|item| !item.notes.trim().is_empty());
+
+    let mut headers = vec!["Ingredient"];
+    if label {
+        headers.push("Label");
+    }
+    headers.extend(["Percentage", "Parts", "Weight (g)"]);
+    if notes {
+        headers.push("Notes");
+    }
+
+    let rows = data
+        .iter()
+        .map(|item| {
+            let mut row = vec![item.ingred_type.insert_spaces().to_string()];
+            if label {
+                row.push(item.label.trim().to_string());
+            }
+            row.extend([
+                format!("{}{percent}", item.percentage),
+                item.parts.to_string(),
+                format!("{}{grams}", item.weight),
+            ]);
+            if notes {
+                row.push(item.notes.trim().to_string());
+            }
+            row
+        })
+        .collect();
+
+    (headers, rows)
+}
+
+fn to_csv(units: f32, data: &[IngredientData]) -> String {
+    let (headers, rows) = table(data, false);
+    let mut csv = format!("Units,{units}\n{}\n", headers.join(","));
+    rows.iter().for_each(|row| {
+        let fields: Vec<String> = row.iter().map(|field| escape_csv(field)).collect();
+        csv.push_str(&(fields.join(",") + "\n"));
+    });
+    csv
+}
+
+fn to_markdown(units: f32, data: &[IngredientData]) -> String {
+    let (headers, rows) = table(data, true);
+    let mut markdown = format!(
+        "**Units:** {units}\n\n| {} |\n|{}\n",
+        headers.join(" | "),
+        " --- |".repeat(headers.len())
+    );
+    rows.iter().for_each(|row| {
+        let fields: Vec<String> = row.iter().map(|field| field.replace('|', "\\|")).collect();
+        markdown.push_str(&format!("| {} |\n", fields.join(" | ")));
+    });
+    markdown
+}
+
+fn to_text(units: f32, data: &[IngredientData]) -> String {
+    let (headers, rows) = table(data, true);
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|ix| {
+            rows.iter()
+                .map(|row| row[ix].chars().count())
+                .chain([headers[ix].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let line = |fields: Vec<&str>| -> String {
+        fields
+            .iter()
+            .zip(&widths)
+            .map(|(field, width)| format!("{field:<width$}"))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+            + "\n"
+    };
+
+    let mut text = format!("Units: {units}\n\n") + &line(headers.clone());
+    rows.iter()
+        .for_each(|row| text.push_str(&line(row.iter().map(|f| f.as_str()).collect())));
+    text
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> Vec<IngredientData> {
+        vec![
+            IngredientData {
+                ingred_type: "Whiskey".into(),
+                percentage: 40.,
+                parts: 1.5,
+                weight: 66.3,
+                ..Default::default()
+            },
+            IngredientData {
+                ingred_type: "GrainAlcohol".into(),
+                percentage: 95.,
+                parts: 1.,
+                weight: 12.,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let expected = "Units,2
+Ingredient,Percentage,Parts,Weight (g)
+Whiskey,40,1.5,66.3
+Grain Alcohol,95,1,12
+";
+        assert_eq!(ExportFormat::Csv.export(2., &data()), expected);
+    }
+
+    #[test]
+    fn test_export_markdown() {
+        let expected = "**Units:** 2
+
+| Ingredient | Percentage | Parts | Weight (g) |
+| --- | --- | --- | --- |
+| Whiskey | 40% | 1.5 | 66.3g |
+| Grain Alcohol | 95% | 1 | 12g |
+";
+        assert_eq!(ExportFormat::Markdown.export(2., &data()), expected);
+    }
+
+    #[test]
+    fn test_export_text() {
+        let expected = "Units: 2
+
+Ingredient     Percentage  Parts  Weight (g)
+Whiskey        40%         1.5    66.3g
+Grain Alcohol  95%         1      12g
+";
+        assert_eq!(ExportFormat::Text.export(2., &data()), expected);
+    }
+
+    #[test]
+    fn test_export_csv_with_label_and_notes() {
+        let mut data = data();
+        data[0].label = "Rittenhouse".into();
+        data[1].notes = "Everclear, or similar".into();
+        let expected = "Units,2
+Ingredient,Label,Percentage,Parts,Weight (g),Notes
+Whiskey,Rittenhouse,40,1.5,66.3,
+Grain Alcohol,,95,1,12,\"Everclear, or similar\"
+";
+        assert_eq!(ExportFormat::Csv.export(2., &data), expected);
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(escape_csv("Smith, \"Cross\""), "\"Smith, \"\"Cross\"\"\"");
+    }
+}
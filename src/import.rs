@@ -0,0 +1,445 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::{types::Type, ui::view::table::ingredient::IngredientData};
+use strum::IntoEnumIterator;
+
+// the most a percentage can be, as in the table's percentage inputs
+const MAX_PERCENTAGE: f32 = 100.;
+
+#[derive(Default)]
+pub struct ParsedRecipe {
+    pub units: Option<f32>,
+    pub ingreds: Vec<IngredientData>,
+    pub unknown: Vec<String>,
+}
+
+/// Parse recipe text (one ingredient per line, e.g., "Gin Plymouth 40% 2 chilled") into
+/// ingredients, where any words between the type and the numbers are its label, and any
+/// after them are its notes
+///
+/// CSV and Markdown table rows, and the rows under an aligned plain text header, are
+/// split into fields instead, taking each value from the column its header names (or,
+/// without a header, from its position) so that labels and notes may hold numbers and
+/// commas
+///
+/// Rows with a percentage outside 0 to 100, or a negative or non-finite number, are
+/// reported as unknown rather than imported
+///
+/// Units lines, blank lines, and the header/separator rows produced by our own exports
+/// are accepted so that exported recipes can be pasted back in
+pub fn parse_recipe(text: &str) -> ParsedRecipe {
+    let mut recipe = ParsedRecipe::default();
+    let mut columns = None;
+    // where each column of an aligned plain text table starts, from its header
+    let mut offsets: Option<Vec<usize>> = None;
+
+    for line in text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !is_separator(line))
+    {
+        let fields = if line.starts_with('|') {
+            split_markdown(line)
+        } else if let Some(offsets) = &offsets {
+            split_aligned(line, offsets)
+        } else if line.contains(',') {
+            split_csv(line)
+        } else if let Some(header) = aligned_offsets(line) {
+            let fields = split_aligned(line, &header);
+            offsets = Some(header);
+            fields
+        } else {
+            parse_words(line, &mut recipe);
+            continue;
+        };
+
+        let name = fields[0].replace(' ', "").to_lowercase();
+        if name.starts_with("units")
+            && let Some(units) = fields
+                .get(1)
+                .and_then(|field| number(field))
+                .filter(|units| in_range(*units, f32::MAX))
+        {
+            recipe.units = Some(units);
+        } else if name.starts_with("ingredient") {
+            columns = Some(Columns::from_header(&fields));
+        } else if let Some(ingred) = parse_fields(&fields, columns) {
+            recipe.ingreds.push(ingred);
+        } else if columns.is_none() {
+            // free text that happens to hold a comma
+            parse_words(line, &mut recipe);
+        } else {
+            recipe.unknown.push(line.to_string());
+        }
+    }
+
+    recipe
+}
+
+/// Which field holds each value of a delimited row
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Columns {
+    label: Option<usize>,
+    percentage: usize,
+    parts: usize,
+    notes: Option<usize>,
+}
+
+impl Columns {
+    /// Find each column by its name in a header row (e.g., "Ingredient,Label,Percentage")
+    fn from_header(fields: &[String]) -> Self {
+        let find = |name: &str| {
+            fields
+                .iter()
+                .position(|field| field.to_lowercase().starts_with(name))
+        };
+        Columns {
+            label: find("label"),
+            percentage: find("percentage").unwrap_or(1),
+            parts: find("parts").unwrap_or(2),
+            notes: find("notes"),
+        }
+    }
+
+    /// Guess the columns of a row without a header: the type, an optional label, the
+    /// percentage and parts, and then notes if the last field isn't a number
+    fn from_row(fields: &[String]) -> Self {
+        let label = fields
+            .get(1)
+            .filter(|field| number(field).is_none())
+            .map(|_| 1);
+        let percentage = label.map_or(1, |label| label + 1);
+        let notes = fields
+            .len()
+            .checked_sub(1)
+            .filter(|&last| last > percentage + 1 && number(&fields[last]).is_none());
+        Columns {
+            label,
+            percentage,
+            parts: percentage + 1,
+            notes,
+        }
+    }
+}
+
+// build an ingredient from the fields of a delimited row, with any words after the
+// type in the first field taken as (the start of) its label
+fn parse_fields(fields: &[String], columns: Option<Columns>) -> Option<IngredientData> {
+    let columns = columns.unwrap_or_else(|| Columns::from_row(fields));
+    let field = |ix: Option<usize>| ix.and_then(|ix| fields.get(ix)).map(|f| f.as_str());
+
+    let words: Vec<&str> = fields[0].split_whitespace().collect();
+    let (ingred_type, rest) = match_type(&words)?;
+    let percentage = field(Some(columns.percentage))
+        .and_then(number)
+        .filter(|percentage| in_range(*percentage, MAX_PERCENTAGE))?;
+    let parts = match field(Some(columns.parts)).filter(|parts| !parts.is_empty()) {
+        Some(parts) => number(parts).filter(|parts| in_range(*parts, f32::MAX))?,
+        None => 0.,
+    };
+    let label = rest
+        .iter()
+        .copied()
+        .chain(field(columns.label))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(IngredientData {
+        ingred_type: ingred_type.to_string().into(),
+        label: label.into(),
+        notes: field(columns.notes).unwrap_or_default().to_string().into(),
+        percentage,
+        parts,
+        ..Default::default()
+    })
+}
+
+// parse a line of free text, e.g., "Gin Plymouth 40% 2 chilled" or "**Units:** 2"
+fn parse_words(line: &str, recipe: &mut ParsedRecipe) {
+    let (words, numbers, notes) = tokenize(line);
+    let name = words.concat().to_lowercase();
+
+    let valid = numbers.iter().all(|number| in_range(*number, f32::MAX));
+
+    if name.starts_with("units") && !numbers.is_empty() && valid {
+        recipe.units = numbers.first().copied();
+    } else if name.starts_with("ingredient") && numbers.is_empty() {
+        // header row from an export
+    } else if let (Some((ingred_type, label)), Some(percentage)) =
+        (match_type(&words), numbers.first())
+        && valid
+        && in_range(*percentage, MAX_PERCENTAGE)
+    {
+        recipe.ingreds.push(IngredientData {
+            ingred_type: ingred_type.to_string().into(),
+            label: label.join(" ").into(),
+            notes: notes.join(" ").into(),
+            percentage: *percentage,
+            parts: numbers.get(1).copied().unwrap_or(0.),
+            ..Default::default()
+        });
+    } else {
+        recipe.unknown.push(line.to_string());
+    }
+}
+
+/// Split a line into the words before its first number, its numbers, and the words after
+fn tokenize(line: &str) -> (Vec<&str>, Vec<f32>, Vec<&str>) {
+    let mut words = vec![];
+    let mut numbers = vec![];
+    let mut notes = vec![];
+
+    line.split(|c: char| c.is_whitespace() || c == '*' || c == ':')
+        .filter(|token| !token.is_empty())
+        .for_each(|token| match number(token) {
+            Some(number) => numbers.push(number),
+            None if numbers.is_empty() => words.push(token),
+            None => notes.push(token),
+        });
+
+    (words, numbers, notes)
+}
+
+/// Split a CSV row into its trimmed fields, where quoted fields may hold commas and
+/// doubled quotes
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+
+    fields
+}
+
+/// Split a Markdown table row into its trimmed fields, unescaping any pipes
+fn split_markdown(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut chars = line.trim_matches('|').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                field.push('|');
+                chars.next();
+            }
+            '|' => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+
+    fields
+}
+
+/// Where each column of an aligned plain text header (e.g., "Ingredient  Percentage")
+/// starts, if line is one
+fn aligned_offsets(line: &str) -> Option<Vec<usize>> {
+    if !line.to_lowercase().starts_with("ingredient") {
+        return None;
+    }
+    // the columns are padded apart by at least two spaces, but the header names may hold
+    // one (e.g., "Weight (g)")
+    let offsets: Vec<usize> = line
+        .char_indices()
+        .filter(|(ix, c)| *ix == 0 || (*c != ' ' && line[..*ix].ends_with("  ")))
+        .map(|(ix, _)| line[..ix].chars().count())
+        .collect();
+    (offsets.len() > 1).then_some(offsets)
+}
+
+/// Split a row of an aligned plain text table into its trimmed fields, by where its
+/// header's columns start
+fn split_aligned(line: &str, offsets: &[usize]) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    offsets
+        .iter()
+        .enumerate()
+        .map(|(ix, start)| {
+            let end = offsets.get(ix + 1).copied().unwrap_or(chars.len());
+            chars[(*start).min(chars.len())..end.min(chars.len())]
+                .iter()
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
+
+// a number, allowing a trailing % or g (e.g., "40%", "66.3g")
+fn number(token: &str) -> Option<f32> {
+    token.trim().trim_end_matches(['%', 'g']).parse().ok()
+}
+
+// whether number is finite and within 0 to max, as the table's inputs require
+fn in_range(number: f32, max: f32) -> bool {
+    (0. ..=max).contains(&number)
+}
+
+/// Find the type spelled out by the most leading words, returning it with the rest
+fn match_type<'a, 'b>(words: &'a [&'b str]) -> Option<(Type, &'a [&'b str])> {
+    (1..=words.len()).rev().find_map(|len| {
+        let name = words[..len].concat().to_lowercase();
+        Type::iter()
+            .find(|t| t.to_string().to_lowercase() == name)
+            .map(|t| (t, &words[len..]))
+    })
+}
+
+fn is_separator(line: &str) -> bool {
+    line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::ExportFormat;
+
+    #[test]
+    fn test_parse_recipe() {
+        let recipe = parse_recipe(
+            "Gin 40% 2\nCampari 25% 1\n\nGrain Alcohol, 95, 1.5\nRum 57% 1 funky, go easy",
+        );
+
+        assert_eq!(recipe.ingreds.len(), 3);
+        assert_eq!(recipe.ingreds[0].ingred_type, "Gin");
+        assert_eq!(recipe.ingreds[0].percentage, 40.);
+        assert_eq!(recipe.ingreds[0].parts, 2.);
+        assert_eq!(recipe.ingreds[1].ingred_type, "GrainAlcohol");
+        assert_eq!(recipe.ingreds[1].parts, 1.5);
+        assert_eq!(recipe.ingreds[2].notes, "funky, go easy");
+        assert_eq!(recipe.unknown, vec!["Campari 25% 1".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_recipe_from_export() {
+        let recipe = parse_recipe(
+            "**Units:** 2
+
+| Ingredient | Percentage | Parts | Weight (g) |
+| --- | --- | --- | --- |
+| Whiskey | 40% | 1.5 | 66.3g |
+",
+        );
+
+        assert_eq!(recipe.units, Some(2.));
+        assert_eq!(recipe.ingreds.len(), 1);
+        assert_eq!(recipe.ingreds[0].parts, 1.5);
+        assert!(recipe.unknown.is_empty());
+    }
+
+    #[test]
+    fn test_parse_recipe_with_label_and_notes() {
+        let recipe = parse_recipe(
+            "Ingredient,Label,Percentage,Parts,Weight (g),Notes
+Rum,Smith & Cross,57,2,88.1,\"funky, so go easy\"
+Grain Alcohol,,95,1,12,",
+        );
+
+        assert_eq!(recipe.ingreds.len(), 2);
+        assert_eq!(recipe.ingreds[0].ingred_type, "Rum");
+        assert_eq!(recipe.ingreds[0].label, "Smith & Cross");
+        assert_eq!(recipe.ingreds[0].notes, "funky, so go easy");
+        assert_eq!(recipe.ingreds[1].ingred_type, "GrainAlcohol");
+        assert_eq!(recipe.ingreds[1].label, "");
+    }
+
+    #[test]
+    fn test_parse_recipe_round_trip() {
+        let data = vec![
+            IngredientData {
+                ingred_type: "Whiskey".into(),
+                label: "Rittenhouse 100".into(),
+                notes: "bottled in bond, \"BiB\" | 50%".into(),
+                percentage: 50.,
+                parts: 1.5,
+                ..Default::default()
+            },
+            IngredientData {
+                ingred_type: "GrainAlcohol".into(),
+                label: "1792".into(),
+                percentage: 95.,
+                parts: 1.,
+                ..Default::default()
+            },
+        ];
+
+        [
+            ExportFormat::Csv,
+            ExportFormat::Markdown,
+            ExportFormat::Text,
+        ]
+        .iter()
+        .for_each(|format| {
+            let recipe = parse_recipe(&format.export(2., &data));
+            let fields: Vec<_> = recipe
+                .ingreds
+                .iter()
+                .map(|ingred| {
+                    (
+                        ingred.ingred_type.to_string(),
+                        ingred.label.to_string(),
+                        ingred.notes.to_string(),
+                        ingred.percentage,
+                        ingred.parts,
+                    )
+                })
+                .collect();
+
+            assert_eq!(recipe.units, Some(2.));
+            assert_eq!(
+                vec![
+                    (
+                        "Whiskey".to_string(),
+                        "Rittenhouse 100".to_string(),
+                        "bottled in bond, \"BiB\" | 50%".to_string(),
+                        50.,
+                        1.5
+                    ),
+                    (
+                        "GrainAlcohol".to_string(),
+                        "1792".to_string(),
+                        "".to_string(),
+                        95.,
+                        1.
+                    ),
+                ],
+                fields
+            );
+            assert!(recipe.unknown.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_parse_recipe_out_of_range() {
+        let lines = [
+            "Whiskey, NaN%, 2",
+            "Whiskey, inf, 2",
+            "Rum, 40, -1",
+            "Gin, 140%, 1",
+            "Gin -40% 2",
+            "Gin 140% 2",
+            "Vodka 40% NaN",
+        ];
+
+        let recipe = parse_recipe(&lines.join("\n"));
+
+        assert!(recipe.ingreds.is_empty());
+        assert_eq!(lines.to_vec(), recipe.unknown);
+    }
+}
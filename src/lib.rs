@@ -2,5 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 mod calc;
+mod export;
+mod import;
 mod types;
 pub mod ui;
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 #![windows_subsystem = "windows"]
-use alc_calc::ui::util::{assets::Assets, window::new_window};
+use alc_calc::ui::util::{assets::Assets, theme::Theme, window::new_window};
 use gpui::{App, Application};
 
 fn main() {
@@ -10,6 +10,7 @@ fn main() {
         .with_assets(Assets {})
         .run(|cx: &mut App| {
             cx.activate(true);
+            Theme::watch(cx);
             new_window(cx);
         });
 }
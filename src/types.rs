@@ -26,13 +26,27 @@ pub enum Type {
     Seltzer,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Category {
     Carbonated,
     Liqueur,
     Hard,
 }
 
+impl Category {
+    /// Categories in the order they're listed to the user
+    pub const ALL: [Category; 3] = [Category::Hard, Category::Liqueur, Category::Carbonated];
+
+    /// Name of the Type that stands in for any member of the category
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Carbonated => "Carbonated",
+            Category::Liqueur => "Liqueur",
+            Category::Hard => "Hard",
+        }
+    }
+}
+
 pub fn match_category(ingred_type: &str) -> Category {
     let ingred_type_e: Type = Type::from_str(ingred_type).unwrap_or(Type::Whiskey);
     match ingred_type_e {
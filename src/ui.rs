@@ -11,21 +11,32 @@ use crate::ui::util::app_menu::{app_dock_menu, app_menu};
 use crate::ui::view::titlebar::Titlebar;
 use crate::ui::{
     comp::{
+        button::{icon_button, text_button},
+        icon::{Icon, IconSize, IconVariant},
         input::text_input::{Copy, Cut, Paste, SelectAll},
         toast::Toast,
+        tooltip::Tooltip,
     },
     util::{
         ctrl::{ActiveCtrl, Ctrl},
-        theme::{ActiveTheme, Theme},
+        keymap::Keymap,
+        session::Session,
+        state::AppState,
+        theme::{ActiveTheme, Theme, ThemeVariant},
         window::{self, WindowBorder, window_border},
     },
-    view::{menu::ThemeMenu, table::data_table::Table},
+    view::{
+        help::HelpOverlay,
+        menu::ThemeMenu,
+        palette::CommandPalette,
+        table::data_table::{Changed, Table},
+    },
 };
 #[cfg(target_os = "windows")]
 use gpui::Empty;
 use gpui::{
     App, ClipboardItem, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding, PromptLevel,
-    SharedString, Subscription, Window, actions, deferred, div, prelude::*,
+    SharedString, Subscription, Window, WindowId, actions, deferred, div, prelude::*, px,
 };
 
 actions!(
@@ -39,11 +50,17 @@ actions!(
         Minimize,
         Toggle,
         Tab,
-        TabPrev
+        TabPrev,
+        NewTab,
+        NextTab,
+        PrevTab,
+        CloseTab,
+        OpenPalette,
+        ToggleHelp
     ]
 );
 
-const CONTEXT: &str = "UI";
+pub const CONTEXT: &str = "UI";
 
 impl ActiveCtrl for App {
     fn ctrl(&self) -> SharedString {
@@ -53,11 +70,15 @@ impl ActiveCtrl for App {
 
 pub struct UI {
     menu: Entity<ThemeMenu>,
+    palette: Entity<CommandPalette>,
+    help: Entity<HelpOverlay>,
     table: Entity<Table>,
+    tables: Vec<Entity<Table>>,
+    window_id: WindowId,
     #[cfg(not(target_os = "windows"))]
     titlebar: Entity<Titlebar>,
     focus_handle: FocusHandle,
-    subscriptions: Vec<Subscription>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl UI {
@@ -65,38 +86,57 @@ impl UI {
     /// - Ctrl
     /// - Theme
     /// - Toast
+    /// - Keymap (if another window hasn't already)
+    /// - AppState (if another window hasn't already)
     ///
     /// Before setting:
-    /// - Keybinds
+    /// - Keybinds (the defaults, less any the user's keymap rebinds)
     /// - Menus (on macOS)
     /// - Subscriptions
-    ///   - menu + table.num_drinks_input both sub to Tab, TabPrev
+    ///   - menu + table's fields all sub to Tab, TabPrev
     ///   - table.num_drinks_input also subs to Toggle
-    ///
-    /// *Note that the UI is notified of new ingredients to recreate subscriptions for
-    /// by subscribing to Table's Add event*
+    ///   - AppState's session is updated with this window's tabs whenever a table emits
+    ///     Changed
+    /// - Tables (restored from AppState's session if this is the first window)
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         Toast::set(cx);
         Ctrl::set(cx);
+        Keymap::set(cx);
         let ctrl = cx.ctrl();
-        cx.bind_keys([
-            KeyBinding::new(&format!("{ctrl}-q"), Quit, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-t"), Toggle, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-n"), NewWindow, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-w"), CloseWindow, Some(CONTEXT)),
-            KeyBinding::new("tab", Tab, Some(CONTEXT)),
-            KeyBinding::new("shift-tab", TabPrev, Some(CONTEXT)),
-            #[cfg(target_os = "macos")]
-            KeyBinding::new(&format!("{ctrl}-h"), Hide, Some(CONTEXT)),
-            #[cfg(target_os = "macos")]
-            KeyBinding::new(&format!("{ctrl}-x"), Cut, Some(CONTEXT)),
-            #[cfg(target_os = "macos")]
-            KeyBinding::new(&format!("{ctrl}-c"), Copy, Some(CONTEXT)),
-            #[cfg(target_os = "macos")]
-            KeyBinding::new(&format!("{ctrl}-v"), Paste, Some(CONTEXT)),
-            #[cfg(target_os = "macos")]
-            KeyBinding::new(&format!("{ctrl}-a"), SelectAll, Some(CONTEXT)),
-        ]);
+        Keymap::bind(
+            cx,
+            CONTEXT,
+            [
+                KeyBinding::new(&format!("{ctrl}-q"), Quit, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-t"), Toggle, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-n"), NewWindow, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-w"), CloseWindow, Some(CONTEXT)),
+                KeyBinding::new("tab", Tab, Some(CONTEXT)),
+                KeyBinding::new("shift-tab", TabPrev, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-t"), NewTab, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-w"), CloseTab, Some(CONTEXT)),
+                KeyBinding::new("ctrl-tab", NextTab, Some(CONTEXT)),
+                KeyBinding::new("ctrl-shift-tab", PrevTab, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-p"), OpenPalette, Some(CONTEXT)),
+                KeyBinding::new("f1", ToggleHelp, Some(CONTEXT)),
+                // leave ? to be typed into fields and the palette's query
+                KeyBinding::new(
+                    "?",
+                    ToggleHelp,
+                    Some(&format!("{CONTEXT} && !TextInput && !CommandPalette")),
+                ),
+                #[cfg(target_os = "macos")]
+                KeyBinding::new(&format!("{ctrl}-h"), Hide, Some(CONTEXT)),
+                #[cfg(target_os = "macos")]
+                KeyBinding::new(&format!("{ctrl}-x"), Cut, Some(CONTEXT)),
+                #[cfg(target_os = "macos")]
+                KeyBinding::new(&format!("{ctrl}-c"), Copy, Some(CONTEXT)),
+                #[cfg(target_os = "macos")]
+                KeyBinding::new(&format!("{ctrl}-v"), Paste, Some(CONTEXT)),
+                #[cfg(target_os = "macos")]
+                KeyBinding::new(&format!("{ctrl}-a"), SelectAll, Some(CONTEXT)),
+            ],
+        );
 
         #[cfg(target_os = "macos")]
         cx.set_menus(app_menu());
@@ -107,52 +147,231 @@ impl UI {
         // prevents fs access on tests
         #[cfg(not(test))]
         Theme::set(cx);
-
-        let table = cx.new(|cx| Table::new(window, cx));
-        cx.subscribe(&table, |this: &mut UI, _table, _event, cx| this.on_add(cx))
-            .detach();
+        AppState::set(cx);
+
+        let window_id = window.window_handle().window_id();
+        let session = AppState::global(cx).update(cx, |state, _cx| state.open(window_id));
+
+        let mut tables: Vec<Entity<Table>> = session
+            .tabs
+            .iter()
+            .map(|recipe| {
+                let table = UI::new_table(window, cx);
+                table.update(cx, |table, cx| table.restore(recipe, window, cx));
+                table
+            })
+            .collect();
+        if tables.is_empty() {
+            tables.push(UI::new_table(window, cx));
+        }
+        let table = tables.get(session.active).unwrap_or(&tables[0]).clone();
 
         UI {
             menu: cx.new(ThemeMenu::new),
+            palette: cx.new(CommandPalette::new),
+            help: cx.new(HelpOverlay::new),
             table,
+            tables,
+            window_id,
             #[cfg(not(target_os = "windows"))]
             titlebar: cx.new(|_| Titlebar::default()),
             focus_handle: cx.focus_handle().tab_index(0).tab_stop(false),
-            subscriptions: vec![
-                cx.subscribe_self(|this: &mut UI, Tab, cx| {
-                    this.menu.update(cx, |menu, cx| menu.hide(cx));
+            _subscriptions: vec![
+                cx.subscribe_self(|this: &mut UI, Tab, cx| this.on_focus_step(cx)),
+                cx.subscribe_self(|this: &mut UI, TabPrev, cx| this.on_focus_step(cx)),
+                cx.subscribe_self(|this, Toggle, cx| {
                     this.table
                         .update(cx, |table, cx| table.show_num_drinks_cursor(cx))
                 }),
-                cx.subscribe_self(|this: &mut UI, TabPrev, cx| {
-                    this.menu.update(cx, |menu, cx| menu.hide(cx));
-                    this.table
-                        .update(cx, |table, cx| table.show_num_drinks_cursor(cx))
+                cx.observe_in(&AppState::global(cx), window, |this, state, window, cx| {
+                    this.sync(state, window, cx)
                 }),
-                cx.subscribe_self(|this, Toggle, cx| {
-                    this.table
-                        .update(cx, |table, cx| table.show_num_drinks_cursor(cx))
+                // switch between the light and dark themes live when following the system
+                cx.observe_window_appearance(window, |_this, _window, cx| {
+                    if cx.theme().variant == ThemeVariant::Auto {
+                        Theme::preview(cx, "Auto");
+                        cx.refresh_windows();
+                    }
                 }),
             ],
         }
     }
 
-    /// Refresh ingreds' subscriptions to Tab, TabPrev events when a new one is added
-    fn on_add(&mut self, cx: &mut Context<Self>) {
-        while self.subscriptions.len() > 3 {
-            self.subscriptions.pop();
+    // hide the menu and any open dropdowns, and show the cursor of whichever field is
+    // focused next
+    fn on_focus_step(&mut self, cx: &mut Context<Self>) {
+        self.menu.update(cx, |menu, cx| menu.hide(cx));
+        self.table.update(cx, |table, cx| {
+            table.show_num_drinks_cursor(cx);
+            table.show_cursor_and_hide_dd(cx);
+        });
+    }
+
+    fn new_table(window: &mut Window, cx: &mut Context<Self>) -> Entity<Table> {
+        let table = cx.new(|cx| Table::new(window, cx));
+        cx.subscribe(&table, |this: &mut UI, _table, _: &Changed, cx| {
+            this.save_session(cx)
+        })
+        .detach();
+        table
+    }
+
+    fn session(&self, cx: &App) -> Session {
+        Session {
+            active: self.active(),
+            tabs: self
+                .tables
+                .iter()
+                .map(|table| table.read(cx).recipe(cx))
+                .collect(),
         }
+    }
+
+    // restore the tables from this window's tabs in AppState if they differ, reusing
+    // the existing tables and keeping at least one
+    fn sync(&mut self, state: Entity<AppState>, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(session) = state.read(cx).window_session(self.window_id).cloned() else {
+            return;
+        };
+        if session == self.session(cx) {
+            return;
+        }
+
+        self.tables.truncate(session.tabs.len().max(1));
+        while self.tables.len() < session.tabs.len() {
+            self.tables.push(UI::new_table(window, cx));
+        }
+        for (table, recipe) in self.tables.iter().zip(&session.tabs) {
+            table.update(cx, |table, cx| table.restore(recipe, window, cx));
+        }
+        self.table = self
+            .tables
+            .get(session.active)
+            .unwrap_or(&self.tables[0])
+            .clone();
+        cx.notify();
+    }
 
-        self.subscriptions.append(&mut vec![
-            cx.subscribe_self(|this: &mut UI, Tab, cx| {
-                this.table
-                    .update(cx, |table, cx| table.show_cursor_and_hide_dd(cx));
-            }),
-            cx.subscribe_self(|this: &mut UI, TabPrev, cx| {
-                this.table
-                    .update(cx, |table, cx| table.show_cursor_and_hide_dd(cx));
-            }),
-        ]);
+    fn save_session(&mut self, cx: &mut Context<Self>) {
+        let session = self.session(cx);
+        let window_id = self.window_id;
+        AppState::global(cx).update(cx, |state, cx| state.set_session(window_id, session, cx));
+    }
+
+    fn active(&self) -> usize {
+        self.tables
+            .iter()
+            .position(|table| table == &self.table)
+            .unwrap_or(0)
+    }
+
+    /// Make the ix-th table active and focus its num_drinks_input
+    fn activate(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(table) = self.tables.get(ix) {
+            self.table = table.clone();
+            self.menu.update(cx, |menu, cx| menu.hide(cx));
+            self.table.read(cx).num_drinks_input.read(cx).focus(window);
+            self.save_session(cx);
+            cx.notify();
+        }
+    }
+
+    fn new_tab(&mut self, _: &NewTab, window: &mut Window, cx: &mut Context<Self>) {
+        let table = UI::new_table(window, cx);
+        self.tables.insert(self.active() + 1, table);
+        self.activate(self.active() + 1, window, cx);
+    }
+
+    fn next_tab(&mut self, _: &NextTab, window: &mut Window, cx: &mut Context<Self>) {
+        self.activate((self.active() + 1) % self.tables.len(), window, cx);
+    }
+
+    fn prev_tab(&mut self, _: &PrevTab, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self
+            .active()
+            .checked_sub(1)
+            .unwrap_or(self.tables.len() - 1);
+        self.activate(ix, window, cx);
+    }
+
+    fn close_tab(&mut self, _: &CloseTab, window: &mut Window, cx: &mut Context<Self>) {
+        self.remove_tab(self.active(), window, cx);
+    }
+
+    /// Remove the ix-th table, keeping at least one around
+    fn remove_tab(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.tables.len() > 1 && ix < self.tables.len() {
+            let active = self.active();
+            self.tables.remove(ix);
+            let active = if ix < active || active == self.tables.len() {
+                active - 1
+            } else {
+                active
+            };
+            self.activate(active, window, cx);
+        }
+    }
+
+    fn render_tabs(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let active = self.active();
+
+        div()
+            .flex()
+            .flex_row()
+            .flex_1()
+            .items_center()
+            .gap_1()
+            .px_2()
+            .pt_2()
+            .text_sm()
+            .overflow_hidden()
+            .children(self.tables.iter().enumerate().map(|(ix, table)| {
+                let label = table.read(cx).label(cx);
+
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap_2()
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .map(|this| match ix == active {
+                        true => this.bg(cx.theme().foreground).text_color(cx.theme().text),
+                        false => this.text_color(cx.theme().subtext),
+                    })
+                    .hover(|this| this.bg(cx.theme().foreground))
+                    .child(div().max_w(px(140.)).truncate().child(text_button(
+                        &format!("tab_{ix}"),
+                        label,
+                        cx.listener(move |this, _, window, cx| this.activate(ix, window, cx)),
+                    )))
+                    .when(self.tables.len() > 1, |this| {
+                        this.child(icon_button(
+                            &format!("close_tab_{ix}"),
+                            Icon::new(cx, IconVariant::Close, IconSize::Small)
+                                .color(cx.theme().subtext),
+                            cx.listener(move |this, _, window, cx| this.remove_tab(ix, window, cx)),
+                        ))
+                    })
+            }))
+            .child(
+                div()
+                    .child(icon_button(
+                        "new_tab",
+                        Icon::new(cx, IconVariant::Plus, IconSize::Small),
+                        cx.listener(|this, _, window, cx| this.new_tab(&NewTab, window, cx)),
+                    ))
+                    .id("new_tab_button")
+                    .tooltip(|window, cx| {
+                        let keybind = Keymap::keybind(window, &NewTab, CONTEXT);
+                        cx.new(|_cx| {
+                            Tooltip::new("New Tab")
+                                .when_some(keybind, |this, keybind| this.keybind(&keybind))
+                        })
+                        .into()
+                    }),
+            )
     }
 
     fn quit(&mut self, _: &Quit, _window: &mut Window, cx: &mut Context<Self>) {
@@ -224,6 +443,30 @@ impl UI {
         cx.emit(Toggle {});
     }
 
+    /// Open the command palette for the table's focused field (or the table itself)
+    fn open_palette(&mut self, _: &OpenPalette, window: &mut Window, cx: &mut Context<Self>) {
+        let table = self.table.read(cx).focus_handle(cx);
+        let target = match table.contains_focused(window, cx) {
+            true => window.focused(cx).unwrap_or(table),
+            false => table,
+        };
+        self.menu.update(cx, |menu, cx| menu.hide(cx));
+        self.palette
+            .update(cx, |palette, cx| palette.show(Some(target), window, cx));
+    }
+
+    fn toggle_help(&mut self, _: &ToggleHelp, window: &mut Window, cx: &mut Context<Self>) {
+        if self.help.read(cx).show {
+            self.help.update(cx, |help, cx| help.hide(window, cx));
+            return;
+        }
+
+        let target = window.focused(cx);
+        self.menu.update(cx, |menu, cx| menu.hide(cx));
+        self.help
+            .update(cx, |help, cx| help.show(target, window, cx));
+    }
+
     fn on_tab(&mut self, _: &Tab, window: &mut Window, cx: &mut Context<Self>) {
         window.focus_next();
         cx.emit(Tab {});
@@ -252,6 +495,12 @@ impl Render for UI {
                 .on_action(cx.listener(Self::quit))
                 .on_action(cx.listener(Self::close))
                 .on_action(cx.listener(Self::create))
+                .on_action(cx.listener(Self::new_tab))
+                .on_action(cx.listener(Self::next_tab))
+                .on_action(cx.listener(Self::prev_tab))
+                .on_action(cx.listener(Self::close_tab))
+                .on_action(cx.listener(Self::open_palette))
+                .on_action(cx.listener(Self::toggle_help))
                 .when(cfg!(target_os = "macos"), |this| {
                     this.on_action(cx.listener(Self::hide))
                         .on_action(cx.listener(Self::minimize))
@@ -276,13 +525,32 @@ impl Render for UI {
 
                     this.child(deferred(titlebar).with_priority(999))
                 })
-                .child(deferred(self.menu.clone()).with_priority(998))
+                .child(
+                    div()
+                        .flex()
+                        .flex_row()
+                        .items_start()
+                        .child(self.render_tabs(cx))
+                        .child(deferred(self.menu.clone()).with_priority(998)),
+                )
                 .child(
                     div()
                         .flex()
                         .justify_center()
                         .child(deferred(Toast::global(cx)).with_priority(997)),
                 )
+                .child(
+                    div()
+                        .flex()
+                        .justify_center()
+                        .child(deferred(self.palette.clone()).with_priority(996)),
+                )
+                .child(
+                    div()
+                        .flex()
+                        .justify_center()
+                        .child(deferred(self.help.clone()).with_priority(996)),
+                )
                 .child(
                     div()
                         .flex()
@@ -305,6 +573,7 @@ impl Focusable for UI {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::util::session::Recipe;
     use gpui::{TestAppContext, VisualTestContext};
 
     #[gpui::test]
@@ -316,7 +585,7 @@ mod tests {
         cx.simulate_keystrokes(&format!("{ctrl}-t"));
         ui.update(cx, |ui, cx| show_menu = ui.menu.read(cx).show);
 
-        assert_eq!(true, show_menu)
+        assert!(show_menu)
     }
 
     #[gpui::test]
@@ -328,7 +597,7 @@ mod tests {
         (0..2).for_each(|_| cx.simulate_keystrokes(&format!("{ctrl}-t")));
         ui.update(cx, |ui, cx| show_menu = ui.menu.read(cx).show);
 
-        assert_eq!(false, show_menu)
+        assert!(!show_menu)
     }
 
     #[gpui::test]
@@ -347,18 +616,146 @@ mod tests {
                 .is_focused(window)
         });
 
-        assert_eq!(true, table_focused)
+        assert!(table_focused)
+    }
+
+    #[gpui::test]
+    fn test_ui_new_tab(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui(cx);
+        let mut session = Session::default();
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab 2 {ctrl}-shift-t"));
+        ui.update(cx, |ui, cx| session = ui.session(cx));
+
+        assert_eq!(2, session.tabs.len());
+        assert_eq!(1, session.active);
+        assert_eq!("2", session.tabs[0].units);
+    }
+
+    #[gpui::test]
+    fn test_ui_next_tab_at_limit(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui(cx);
+        let mut active = 1;
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("{ctrl}-shift-t {ctrl}-shift-t ctrl-tab"));
+        ui.update(cx, |ui, _cx| active = ui.active());
+
+        assert_eq!(0, active);
+    }
+
+    #[gpui::test]
+    fn test_ui_palette_new_tab(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui(cx);
+        let mut session = Session::default();
+        let mut palette_shown = true;
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab 2 {ctrl}-shift-p n e w space t enter"));
+        ui.update(cx, |ui, cx| {
+            session = ui.session(cx);
+            palette_shown = ui.palette.read(cx).show;
+        });
+
+        assert_eq!(2, session.tabs.len());
+        assert_eq!(1, session.active);
+        assert!(!palette_shown);
+    }
+
+    #[gpui::test]
+    fn test_ui_toggle_help(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui(cx);
+        let mut shown = vec![];
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("?");
+        ui.update(cx, |ui, cx| shown.push(ui.help.read(cx).show));
+        cx.simulate_keystrokes("f1");
+        ui.update(cx, |ui, cx| shown.push(ui.help.read(cx).show));
+        cx.simulate_keystrokes("tab ? escape");
+        ui.update(cx, |ui, cx| shown.push(ui.help.read(cx).show));
+
+        assert_eq!(vec![true, false, false], shown);
+    }
+
+    #[gpui::test]
+    fn test_ui_close_tab(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui(cx);
+        let mut session = Session::default();
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab 2 {ctrl}-shift-t 3 ctrl-shift-tab"));
+        cx.simulate_keystrokes(&format!("{ctrl}-shift-w {ctrl}-shift-w"));
+        ui.update(cx, |ui, cx| session = ui.session(cx));
+
+        assert_eq!(1, session.tabs.len());
+        assert_eq!("3", session.tabs[0].units);
+    }
+
+    #[gpui::test]
+    fn test_ui_windows_session(cx: &mut TestAppContext) {
+        Theme::test(cx);
+        AppState::test(cx);
+        cx.update(Ctrl::set);
+        let windows = [cx.add_window(UI::new), cx.add_window(UI::new)];
+        let mut num_tabs = vec![];
+
+        windows.iter().for_each(|ui| {
+            ui.update(cx, |ui, window, cx| ui.new_tab(&NewTab, window, cx))
+                .unwrap();
+        });
+        cx.update(|cx| num_tabs.push(AppState::global(cx).read(cx).session.tabs.len()));
+        windows.iter().rev().for_each(|ui| {
+            ui.update(cx, |_ui, window, _cx| window.remove_window())
+                .unwrap();
+            cx.update(|cx| num_tabs.push(AppState::global(cx).read(cx).session.tabs.len()));
+        });
+
+        // the last window's tabs are kept for the next launch
+        assert_eq!(vec![4, 2, 2], num_tabs);
+    }
+
+    #[gpui::test]
+    fn test_ui_follows_state(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui(cx);
+        let window_id = cx.update(|window, _cx| window.window_handle().window_id());
+        let mut session = Session::default();
+        let recipe = |units: &str| Recipe {
+            units: units.into(),
+            ..Default::default()
+        };
+
+        cx.update(|_window, cx| {
+            AppState::global(cx).update(cx, |state, cx| {
+                let tabs = vec![recipe("3"), recipe("4")];
+                state.set_session(window_id, Session { active: 1, tabs }, cx)
+            })
+        });
+        cx.run_until_parked();
+        ui.update(cx, |ui, cx| session = ui.session(cx));
+
+        assert_eq!(1, session.active);
+        assert_eq!(
+            vec!["3", "4"],
+            session
+                .tabs
+                .iter()
+                .map(|t| t.units.as_str())
+                .collect::<Vec<_>>()
+        );
     }
 
     pub fn setup_ui(cx: &mut TestAppContext) -> (Entity<UI>, &mut VisualTestContext, SharedString) {
         Theme::test(cx);
+        AppState::test(cx);
         let mut ctrl: SharedString = "".into();
         cx.update(|cx| {
             Ctrl::set(cx);
             ctrl = cx.ctrl();
         });
 
-        let (ui, cx) = cx.add_window_view(|window, cx| UI::new(window, cx));
+        let (ui, cx) = cx.add_window_view(UI::new);
         (ui, cx, ctrl)
     }
 }
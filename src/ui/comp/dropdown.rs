@@ -4,31 +4,44 @@
 // Scrollbar from: https://github.com/zed-industries/zed/blob/main/crates/gpui/examples/data_table.rs
 
 use crate::{
-    types::Type,
+    types::{Category, Type, match_category},
     ui::{
         comp::{
             button::{button, text_button},
             icon::{Icon, IconSize, IconVariant},
         },
-        util::{str::Spaceable, theme::ActiveTheme},
-        view::table::data_table::MAX_ITEMS,
+        util::{fuzzy::fuzzy_filter, keymap::Keymap, str::Spaceable, theme::ActiveTheme},
     },
 };
 use gpui::{
-    App, FocusHandle, Focusable, KeyBinding, Pixels, ScrollStrategy, SharedString,
-    UniformListScrollHandle, Window, actions, deferred, div, prelude::*, px, uniform_list,
+    App, EventEmitter, FocusHandle, Focusable, KeyBinding, KeyContext, KeyDownEvent, Pixels,
+    ScrollStrategy, SharedString, UniformListScrollHandle, Window, actions, deferred, div,
+    prelude::*, px, uniform_list,
 };
 use std::ops::Range;
-use strum::{EnumCount, IntoEnumIterator};
+use strum::IntoEnumIterator;
 
 actions!(dropdown, [Escape, Enter, Next, Prev, Select]);
 
 const CONTEXT: &str = "Dropdown";
-const SCROLLBAR_THUMB_WIDTH: Pixels = px(8.);
-const SCROLLBAR_THUMB_HEIGHT: Pixels = px(96.);
+pub const SCROLLBAR_THUMB_WIDTH: Pixels = px(8.);
+pub const SCROLLBAR_THUMB_HEIGHT: Pixels = px(96.);
+// only one list is open at a time, so it just needs to sit above the table and below
+// the Toast, ThemeMenu, and Titlebar
+const LIST_PRIORITY: usize = 1;
+
+#[derive(Clone, Copy)]
+enum Row {
+    Header(Category),
+    // index into types
+    Item(usize),
+}
 
 pub struct Dropdown {
     types: Vec<SharedString>,
+    // types matching query, grouped under their category's header
+    rows: Vec<Row>,
+    query: String,
     pub current: SharedString,
     prev: Option<SharedString>,
     pub show: bool,
@@ -40,35 +53,42 @@ pub struct Dropdown {
 }
 
 impl Dropdown {
-    pub fn new(id: usize, cx: &mut Context<Self>, tab_index: isize) -> Self {
-        cx.bind_keys([
-            KeyBinding::new("escape", Escape, Some(CONTEXT)),
-            KeyBinding::new("enter", Enter, Some(CONTEXT)),
-            KeyBinding::new("up", Prev, Some(CONTEXT)),
-            KeyBinding::new("k", Prev, Some(CONTEXT)),
-            KeyBinding::new("down", Next, Some(CONTEXT)),
-            KeyBinding::new("j", Next, Some(CONTEXT)),
-            KeyBinding::new("enter", Select, Some(CONTEXT)),
-        ]);
+    pub fn new(id: usize, cx: &mut Context<Self>) -> Self {
+        Keymap::bind(
+            cx,
+            CONTEXT,
+            [
+                KeyBinding::new("escape", Escape, Some(CONTEXT)),
+                KeyBinding::new("enter", Enter, Some(CONTEXT)),
+                KeyBinding::new("up", Prev, Some(CONTEXT)),
+                KeyBinding::new("k", Prev, Some(&format!("{CONTEXT} && !filtering"))),
+                KeyBinding::new("down", Next, Some(CONTEXT)),
+                KeyBinding::new("j", Next, Some(&format!("{CONTEXT} && !filtering"))),
+                KeyBinding::new("enter", Select, Some(CONTEXT)),
+            ],
+        );
 
         let types: Vec<SharedString> = Type::iter()
             .map(|t| SharedString::from(t.to_string()))
             .collect();
-        let current: SharedString = "Whiskey".into();
-        let focused_item = Dropdown::index_of(&types, &current);
-        let focus_handle = cx.focus_handle().tab_index(tab_index).tab_stop(true);
+        let focus_handle = cx.focus_handle().tab_stop(true);
 
-        Self {
+        let mut dropdown = Self {
             types,
-            current,
+            rows: Vec::new(),
+            query: String::new(),
+            current: "Whiskey".into(),
             prev: None,
             show: false,
-            count: Type::COUNT,
+            count: 0,
             id,
-            focused_item,
+            focused_item: 0,
             focus_handle,
             scroll_handle: UniformListScrollHandle::new(),
-        }
+        };
+        dropdown.filter(String::new(), cx);
+        dropdown.focused_item = dropdown.index_of(&dropdown.current);
+        dropdown
     }
 
     pub fn focus(&self, window: &mut Window) {
@@ -89,6 +109,13 @@ impl Dropdown {
         }
     }
 
+    fn set_current(&mut self, current: SharedString, cx: &mut Context<Self>) {
+        if self.current != current {
+            self.current = current;
+            cx.emit(TypeChanged {});
+        }
+    }
+
     fn update(
         &mut self,
         window: &mut Window,
@@ -96,21 +123,35 @@ impl Dropdown {
         val: SharedString,
         toggle: bool,
     ) {
-        self.focused_item = Dropdown::index_of(&self.types, &val);
-        self.current = val;
+        // while filtering, focused_item is already the row of val
+        if toggle || self.query.is_empty() {
+            self.filter(String::new(), cx);
+            self.focused_item = self.index_of(&val);
+        }
+        self.set_current(val, cx);
         if toggle {
             self.toggle(cx);
+            cx.emit(TypeSelected {});
         }
         self.focus_handle.focus(window);
     }
 
+    pub fn set(&mut self, val: SharedString, cx: &mut Context<Self>) {
+        self.filter(String::new(), cx);
+        self.focused_item = self.index_of(&val);
+        self.set_current(val, cx);
+        self.scroll();
+        cx.notify();
+    }
+
     fn escape(&mut self, _: &Escape, _window: &mut Window, cx: &mut Context<Self>) {
         self.show = false;
+        self.filter(String::new(), cx);
         if self.prev.is_some() {
             let current = self.prev.clone().unwrap_or("Whiskey".into());
-            self.focused_item = Dropdown::index_of(&self.types, &current);
-            self.current = current;
+            self.set_current(current, cx);
         }
+        self.focused_item = self.index_of(&self.current);
         self.scroll();
         cx.notify();
     }
@@ -124,76 +165,171 @@ impl Dropdown {
 
     pub fn hide(&mut self, cx: &mut Context<Self>) {
         if self.show {
+            self.filter(String::new(), cx);
             if self.prev.is_some() {
                 let current = self.prev.clone().unwrap_or("Whiskey".into());
-                self.focused_item = Dropdown::index_of(&self.types, &current);
-                self.current = current;
+                self.set_current(current, cx);
             }
+            self.focused_item = self.index_of(&self.current);
             self.show = false;
             cx.notify();
         }
     }
 
-    // types is guaranteed to be nonempty, so default to 0th type to avoid panicking
+    /// Narrow the list to types fuzzy-matching query, previewing the best match
+    fn filter(&mut self, query: String, cx: &mut Context<Self>) {
+        let names: Vec<SharedString> = self.types.iter().map(Dropdown::label).collect();
+        let matches = fuzzy_filter(&query, &names);
+
+        let mut rows = Vec::new();
+        for category in Category::ALL {
+            let mut items: Vec<usize> = matches
+                .iter()
+                .copied()
+                .filter(|ix| match_category(&self.types[*ix]) == category)
+                .collect();
+            if items.is_empty() {
+                continue;
+            }
+            // the category's stand-in type leads its section
+            items.sort_by_key(|ix| self.types[*ix].as_ref() != category.name());
+            rows.push(Row::Header(category));
+            rows.extend(items.into_iter().map(Row::Item));
+        }
+        self.rows = rows;
+        self.count = self.rows.len();
+        self.query = query;
+
+        if !self.query.is_empty()
+            && let Some(best) = matches.first()
+        {
+            self.focused_item = self
+                .rows
+                .iter()
+                .position(|row| matches!(row, Row::Item(ix) if ix == best))
+                .unwrap_or(0);
+            self.set_current(self.types[*best].clone(), cx);
+            self.scroll();
+        }
+    }
+
+    /// Type-ahead: printable keys that aren't bound to an action edit the filter (opening
+    /// the list if needed), and backspace removes from it
+    fn on_key_down(&mut self, event: &KeyDownEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        if keystroke.modifiers.control || keystroke.modifiers.platform {
+            return;
+        }
+
+        let mut query = self.query.clone();
+        match (keystroke.key.as_str(), &keystroke.key_char) {
+            ("backspace", _) if self.show => {
+                query.pop();
+            }
+            (_, Some(key_char))
+                if !key_char.chars().any(char::is_control)
+                    && (!key_char.trim().is_empty() || !query.is_empty()) =>
+            {
+                if !self.show {
+                    self.prev = Some(self.current.clone());
+                    self.show = true;
+                }
+                query.push_str(key_char);
+            }
+            _ => return,
+        }
+
+        cx.stop_propagation();
+        self.filter(query, cx);
+        cx.notify();
+    }
+
+    fn item(&self, ix: usize) -> Option<SharedString> {
+        match self.rows.get(ix) {
+            Some(Row::Item(ix)) => self.types.get(*ix).cloned(),
+            _ => None,
+        }
+    }
+
+    /// Move to the next or previous item, wrapping around and skipping headers
+    fn step(&mut self, forward: bool) {
+        for _ in 0..self.count {
+            self.focused_item = if forward {
+                (self.focused_item + 1) % self.count
+            } else {
+                (self.focused_item + self.count - 1) % self.count
+            };
+            if let Some(Row::Item(_)) = self.rows.get(self.focused_item) {
+                break;
+            }
+        }
+    }
 
     fn select(&mut self, _: &Select, window: &mut Window, cx: &mut Context<Self>) {
-        self.update(
-            window,
-            cx,
-            self.types
-                .get(self.focused_item)
-                .unwrap_or(&self.types[0])
-                .clone(),
-            true,
-        );
+        match self.item(self.focused_item) {
+            Some(item) => self.update(window, cx, item, true),
+            None => self.escape(&Escape, window, cx),
+        }
         cx.notify();
     }
 
     fn next(&mut self, _: &Next, window: &mut Window, cx: &mut Context<Self>) {
-        if self.focused_item < (self.count - 1) {
-            self.focused_item += 1;
-        } else {
-            self.focused_item = 0;
+        if self.count == 0 {
+            return;
         }
+        self.step(true);
         self.scroll();
-        self.update(
-            window,
-            cx,
-            self.types
-                .get(self.focused_item)
-                .unwrap_or(&self.types[0])
-                .clone(),
-            false,
-        );
+        if let Some(item) = self.item(self.focused_item) {
+            self.update(window, cx, item, false);
+        }
         cx.notify();
     }
 
     fn prev(&mut self, _: &Prev, window: &mut Window, cx: &mut Context<Self>) {
-        if self.focused_item == 0 {
-            self.focused_item = self.count - 1;
-        } else {
-            self.focused_item -= 1;
+        if self.count == 0 {
+            return;
         }
+        self.step(false);
         self.scroll();
-        self.update(
-            window,
-            cx,
-            self.types
-                .get(self.focused_item)
-                .unwrap_or(&self.types[0])
-                .clone(),
-            false,
-        );
+        if let Some(item) = self.item(self.focused_item) {
+            self.update(window, cx, item, false);
+        }
         cx.notify();
     }
 
     fn scroll(&mut self) {
-        self.scroll_handle
-            .scroll_to_item(self.focused_item, ScrollStrategy::Top);
+        // keep a section's header in view along with its first item
+        let ix = match self.focused_item.checked_sub(1).map(|ix| self.rows[ix]) {
+            Some(Row::Header(_)) => self.focused_item - 1,
+            _ => self.focused_item,
+        };
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+    }
+
+    // falls back to the first item, since headers can't be focused
+    fn index_of(&self, val: &SharedString) -> usize {
+        self.rows
+            .iter()
+            .position(|row| matches!(row, Row::Item(ix) if self.types[*ix] == *val))
+            .or_else(|| self.rows.iter().position(|row| matches!(row, Row::Item(_))))
+            .unwrap_or(0)
+    }
+
+    /// The name shown for a type, both in the list and once picked, where a category's
+    /// stand-in type reads as e.g., "Other Liqueur"
+    fn label(val: &SharedString) -> SharedString {
+        match Category::ALL.iter().find(|c| c.name() == val.as_ref()) {
+            Some(category) => format!("Other {}", Dropdown::header(*category).0).into(),
+            None => val.insert_spaces(),
+        }
     }
 
-    fn index_of(types: &[SharedString], val: &SharedString) -> usize {
-        types.iter().position(|v| v == val).unwrap_or(0)
+    fn header(category: Category) -> (&'static str, IconVariant) {
+        match category {
+            Category::Hard => ("Hard Liquor", IconVariant::Bottle),
+            Category::Liqueur => ("Liqueur", IconVariant::Glass),
+            Category::Carbonated => ("Carbonated", IconVariant::Bubbles),
+        }
     }
 }
 
@@ -218,38 +354,44 @@ impl Render for Dropdown {
             (table_height - SCROLLBAR_THUMB_HEIGHT + px(4.)).max(px(4.)),
         );
 
-        deferred(
-            div()
-                .flex()
-                .flex_col()
-                .key_context(CONTEXT)
-                .when(self.show, |this| {
-                    this.on_action(cx.listener(Self::escape))
-                        .on_action(cx.listener(Self::select))
-                        .on_action(cx.listener(Self::next))
-                        .on_action(cx.listener(Self::prev))
-                })
-                .when(!self.show, |this| this.on_action(cx.listener(Self::show)))
-                .track_focus(&self.focus_handle)
-                .bg(cx.theme().field)
-                .text_color(cx.theme().field_text)
-                .border_1()
-                .border_color(cx.theme().field)
-                .focus(|this| this.border_color(cx.theme().cursor))
-                .px_2()
-                .py_1()
-                .rounded_md()
-                .child(button(
-                    &format!("dropdown_{}", self.id),
-                    self.current.clone().insert_spaces(),
-                    Icon::new(cx, IconVariant::Chevron, IconSize::Small)
-                        .color(cx.theme().field_text),
-                    cx.listener(move |this, _, _window, cx| {
-                        this.toggle(cx);
-                    }),
-                ))
-                .when(self.show, |this| {
-                    this.child(
+        let mut context = KeyContext::default();
+        context.add(CONTEXT);
+        if !self.query.is_empty() {
+            context.add("filtering");
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .key_context(context)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .when(self.show, |this| {
+                this.on_action(cx.listener(Self::escape))
+                    .on_action(cx.listener(Self::select))
+                    .on_action(cx.listener(Self::next))
+                    .on_action(cx.listener(Self::prev))
+            })
+            .when(!self.show, |this| this.on_action(cx.listener(Self::show)))
+            .track_focus(&self.focus_handle)
+            .bg(cx.theme().field)
+            .text_color(cx.theme().field_text)
+            .border_1()
+            .border_color(cx.theme().field)
+            .focus(|this| this.border_color(cx.theme().cursor))
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .child(button(
+                &format!("dropdown_{}", self.id),
+                Dropdown::label(&self.current),
+                Icon::new(cx, IconVariant::Chevron, IconSize::Small).color(cx.theme().field_text),
+                cx.listener(move |this, _, _window, cx| {
+                    this.toggle(cx);
+                }),
+            ))
+            .when(self.show, |this| {
+                this.child(
+                    deferred(
                         div()
                             .key_context(CONTEXT)
                             .flex()
@@ -263,6 +405,28 @@ impl Render for Dropdown {
                             .w_full()
                             .h_48()
                             .id(format!("ingreds_list_container_{}", self.id).into_element())
+                            .child(
+                                div()
+                                    .px_1()
+                                    .pb_1()
+                                    .mb_1()
+                                    .border_b_1()
+                                    .border_color(cx.theme().background)
+                                    .when(self.query.is_empty(), |this| {
+                                        this.text_color(cx.theme().subtext)
+                                            .child("Type to filter...")
+                                    })
+                                    .when(!self.query.is_empty(), |this| {
+                                        this.child(self.query.clone())
+                                    })
+                                    .when(self.count == 0, |this| {
+                                        this.child(
+                                            div()
+                                                .text_color(cx.theme().subtext)
+                                                .child("No matches"),
+                                        )
+                                    }),
+                            )
                             .child(
                                 uniform_list(
                                     "ingreds_list",
@@ -270,13 +434,37 @@ impl Render for Dropdown {
                                     cx.processor(|this, range: Range<usize>, _window, cx| {
                                         range
                                             .map(|ix| {
+                                                if let Some(Row::Header(category)) =
+                                                    this.rows.get(ix).copied()
+                                                {
+                                                    let (label, icon) = Dropdown::header(category);
+                                                    return div()
+                                                        .flex()
+                                                        .flex_row()
+                                                        .items_center()
+                                                        .gap_1()
+                                                        .px_1()
+                                                        .text_color(cx.theme().subtext)
+                                                        .child(
+                                                            div().size_4().child(
+                                                                Icon::new(
+                                                                    cx,
+                                                                    icon,
+                                                                    IconSize::Small,
+                                                                )
+                                                                .color(cx.theme().subtext),
+                                                            ),
+                                                        )
+                                                        .child(label)
+                                                        .into_any_element();
+                                                }
+
                                                 // 0th type is guaranteed to exist, so this prevents
                                                 // panicking if underlying uniform_list has a bug
                                                 let item = this
-                                                    .types
-                                                    .get(ix)
-                                                    .unwrap_or(&this.types[0])
-                                                    .clone();
+                                                    .item(ix)
+                                                    .unwrap_or_else(|| this.types[0].clone());
+                                                let name = Dropdown::label(&item);
                                                 div()
                                                     .rounded_md()
                                                     .px_1()
@@ -286,7 +474,7 @@ impl Render for Dropdown {
                                                     })
                                                     .child(text_button(
                                                         &format!("dropdown_item_{ix}"),
-                                                        item.clone().insert_spaces(),
+                                                        name,
                                                         cx.listener(move |this, _, window, cx| {
                                                             this.update(
                                                                 window,
@@ -296,6 +484,7 @@ impl Render for Dropdown {
                                                             );
                                                         }),
                                                     ))
+                                                    .into_any_element()
                                             })
                                             .collect()
                                     }),
@@ -305,7 +494,8 @@ impl Render for Dropdown {
                                     cx.stop_propagation();
                                     this.escape(&Escape, window, cx);
                                 }))
-                                .h_full(),
+                                .flex_1()
+                                .min_h_0(),
                             )
                             .child(
                                 // scrollbar
@@ -323,12 +513,22 @@ impl Render for Dropdown {
                                     .rounded_lg(),
                             ),
                     )
-                }),
-        )
-        .with_priority(MAX_ITEMS - self.id)
+                    .with_priority(LIST_PRIORITY),
+                )
+            })
     }
 }
 
+/// Emitted whenever the current type changes, including while previewing types
+pub struct TypeChanged {}
+
+impl EventEmitter<TypeChanged> for Dropdown {}
+
+/// Emitted when the user picks a type, closing the list
+pub struct TypeSelected {}
+
+impl EventEmitter<TypeSelected> for Dropdown {}
+
 impl Focusable for Dropdown {
     fn focus_handle(&self, _: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -342,7 +542,8 @@ mod tests {
     use super::*;
     use gpui::{Entity, TestAppContext, VisualTestContext};
 
-    const MAX_INDEX: usize = 17;
+    // 18 types plus 3 category headers
+    const LAST_ROW: usize = 20;
 
     #[gpui::test]
     fn test_dropdown_update(cx: &mut TestAppContext) {
@@ -375,7 +576,7 @@ mod tests {
         let (dropdown, cx) = setup_dropdown(cx);
         dropdown.update(cx, |menu, _cx| {
             menu.show = true;
-            menu.focused_item = MAX_INDEX;
+            menu.focused_item = LAST_ROW;
         });
         let mut result = 0;
 
@@ -383,7 +584,7 @@ mod tests {
         cx.simulate_keystrokes("j");
         dropdown.update(cx, |dropdown, _cx| result = dropdown.focused_item);
 
-        assert_eq!(0, result)
+        assert_eq!(1, result)
     }
 
     #[gpui::test]
@@ -391,7 +592,7 @@ mod tests {
         let (dropdown, cx) = setup_dropdown(cx);
         dropdown.update(cx, |dropdown, _cx| {
             dropdown.show = true;
-            dropdown.focused_item = 0;
+            dropdown.focused_item = 1;
         });
         let mut result = 0;
 
@@ -399,11 +600,106 @@ mod tests {
         cx.simulate_keystrokes("k");
         dropdown.update(cx, |dropdown, _cx| result = dropdown.focused_item);
 
-        assert_eq!(MAX_INDEX, result)
+        assert_eq!(LAST_ROW, result)
+    }
+
+    #[gpui::test]
+    fn test_dropdown_next_skips_header(cx: &mut TestAppContext) {
+        let (dropdown, cx) = setup_dropdown(cx);
+        dropdown.update(cx, |dropdown, cx| dropdown.set("GrainAlcohol".into(), cx));
+        let mut result = String::new().into();
+
+        cx.focus(&dropdown);
+        cx.simulate_keystrokes("enter j enter");
+        dropdown.update(cx, |dropdown, _cx| result = dropdown.current.clone());
+
+        assert_eq!(SharedString::from("Liqueur"), result);
+    }
+
+    #[gpui::test]
+    fn test_dropdown_filter_groups(cx: &mut TestAppContext) {
+        let (dropdown, cx) = setup_dropdown(cx);
+        let mut result = (0, 0);
+
+        cx.focus(&dropdown);
+        cx.simulate_keystrokes("enter b e e r");
+        dropdown.update(cx, |dropdown, _cx| {
+            result = (dropdown.count, dropdown.focused_item)
+        });
+
+        assert_eq!((3, 1), result);
+    }
+
+    #[gpui::test]
+    fn test_dropdown_filter_select(cx: &mut TestAppContext) {
+        let (dropdown, cx) = setup_dropdown(cx);
+        dropdown.update(cx, |menu, _cx| menu.show = true);
+        let mut result = String::new().into();
+
+        cx.focus(&dropdown);
+        cx.simulate_keystrokes("g a l c enter");
+        dropdown.update(cx, |dropdown, _cx| result = dropdown.current.clone());
+
+        assert_eq!(SharedString::from("GrainAlcohol"), result);
+    }
+
+    #[gpui::test]
+    fn test_dropdown_filter_j_k(cx: &mut TestAppContext) {
+        let (dropdown, cx) = setup_dropdown(cx);
+        dropdown.update(cx, |menu, _cx| menu.show = true);
+        let mut result = String::new();
+
+        cx.focus(&dropdown);
+        cx.simulate_keystrokes("s k");
+        dropdown.update(cx, |dropdown, _cx| result = dropdown.query.clone());
+
+        assert_eq!("sk", result);
+    }
+
+    #[gpui::test]
+    fn test_dropdown_filter_escape(cx: &mut TestAppContext) {
+        let (dropdown, cx) = setup_dropdown(cx);
+        let mut result = (0, String::new().into());
+
+        cx.focus(&dropdown);
+        cx.simulate_keystrokes("enter b e e r escape");
+        dropdown.update(cx, |dropdown, _cx| {
+            result = (dropdown.count, dropdown.current.clone())
+        });
+
+        assert_eq!((LAST_ROW + 1, SharedString::from("Whiskey")), result);
+    }
+
+    #[gpui::test]
+    fn test_dropdown_type_ahead(cx: &mut TestAppContext) {
+        let (dropdown, cx) = setup_dropdown(cx);
+        let mut result = String::new().into();
+
+        cx.focus(&dropdown);
+        cx.simulate_keystrokes("r u m enter");
+        dropdown.update(cx, |dropdown, _cx| result = dropdown.current.clone());
+
+        assert_eq!(SharedString::from("Rum"), result);
+    }
+
+    #[gpui::test]
+    fn test_dropdown_stand_in_label(cx: &mut TestAppContext) {
+        let (dropdown, cx) = setup_dropdown(cx);
+        let mut result = String::new().into();
+
+        cx.focus(&dropdown);
+        cx.simulate_keystrokes("enter o t h e r space l i q enter");
+        dropdown.update(cx, |dropdown, _cx| result = dropdown.current.clone());
+
+        assert_eq!(SharedString::from("Liqueur"), result);
+        assert_eq!(
+            SharedString::from("Other Hard Liquor"),
+            Dropdown::label(&"Hard".into())
+        );
     }
 
     fn setup_dropdown(cx: &mut TestAppContext) -> (Entity<Dropdown>, &mut VisualTestContext) {
         Theme::test(cx);
-        cx.add_window_view(|_window, cx| Dropdown::new(0, cx, 1))
+        cx.add_window_view(|_window, cx| Dropdown::new(0, cx))
     }
 }
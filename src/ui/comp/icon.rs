@@ -6,8 +6,12 @@ use gpui::{App, Div, Hsla, SharedString, Stateful, Window, prelude::*, svg};
 
 #[derive(PartialEq)]
 pub enum IconVariant {
+    Bottle,
+    Bubbles,
     Chevron,
     Close,
+    Glass,
+    Grip,
     Minus,
     Plus,
     Theme,
@@ -16,8 +20,12 @@ pub enum IconVariant {
 impl IconVariant {
     fn path(variant: &IconVariant) -> SharedString {
         match variant {
+            IconVariant::Bottle => "bottle.svg",
+            IconVariant::Bubbles => "bubbles.svg",
             IconVariant::Chevron => "chevron.svg",
             IconVariant::Close => "close.svg",
+            IconVariant::Glass => "glass.svg",
+            IconVariant::Grip => "grip.svg",
             IconVariant::Minus => "minus.svg",
             IconVariant::Plus => "plus.svg",
             IconVariant::Theme => "image.svg",
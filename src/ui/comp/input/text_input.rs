@@ -3,16 +3,19 @@
 
 // Adapted from: https://github.com/zed-industries/zed/blob/main/crates/gpui/examples/input.rs
 
-use crate::ui::{
-    ActiveCtrl,
-    comp::input::{cursor_state::CursorState, text_element::TextElement},
-    util::theme::ActiveTheme,
+use crate::{
+    calc::round_to_place,
+    ui::{
+        ActiveCtrl,
+        comp::input::{cursor_state::CursorState, text_element::TextElement},
+        util::{keymap::Keymap, theme::ActiveTheme},
+    },
 };
 use gpui::{
     App, Bounds, ClipboardItem, Context, CursorStyle, Entity, EntityInputHandler, EventEmitter,
     FocusHandle, Focusable, KeyBinding, KeyDownEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, Point, ShapedLine, SharedString, Subscription, UTF16Selection, Window,
-    actions, div, point, prelude::*, px,
+    MouseUpEvent, Pixels, Point, ScrollWheelEvent, ShapedLine, SharedString, Subscription,
+    UTF16Selection, Window, actions, div, point, prelude::*, px,
 };
 use std::ops::Range;
 use unicode_segmentation::UnicodeSegmentation;
@@ -21,18 +24,32 @@ actions!(
     text_input,
     [
         Backspace,
+        BackspaceWord,
         Delete,
+        DeleteWord,
         Left,
         Right,
+        WordLeft,
+        WordRight,
         SelectLeft,
         SelectRight,
+        SelectWordLeft,
+        SelectWordRight,
         SelectAll,
+        SelectHome,
+        SelectEnd,
         Home,
         End,
+        Undo,
+        Redo,
         ShowCharacterPalette,
         Paste,
         Cut,
         Copy,
+        StepUp,
+        StepDown,
+        StepUpFine,
+        StepDownFine,
     ]
 );
 
@@ -42,6 +59,69 @@ enum InputEvent {
 }
 
 const CONTEXT: &str = "TextInput";
+// macOS moves by word with alt, since cmd-left/right already go to the line's ends
+const WORD: &str = if cfg!(target_os = "macos") {
+    "alt"
+} else {
+    "ctrl"
+};
+const MAX_HISTORY: usize = 100;
+// how far the wheel has to scroll to step a numeric input once
+const WHEEL_STEP: Pixels = px(20.);
+
+/// Limits for an input that only holds a number
+#[derive(Clone, Copy)]
+pub struct Numeric {
+    pub min: f32,
+    pub max: f32,
+    pub allow_decimal: bool,
+}
+
+impl Default for Numeric {
+    fn default() -> Self {
+        Self {
+            min: 0.,
+            max: f32::MAX,
+            allow_decimal: true,
+        }
+    }
+}
+
+impl Numeric {
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    pub fn allow_decimal(mut self, allow_decimal: bool) -> Self {
+        self.allow_decimal = allow_decimal;
+        self
+    }
+
+    /// Whether text could be (the start of) a number, so typing anything else is rejected
+    fn accepts(&self, text: &str) -> bool {
+        text.char_indices().all(|(ix, c)| {
+            c.is_ascii_digit()
+                || c.is_whitespace()
+                || (c == '.' && self.allow_decimal)
+                || (c == '-' && self.min < 0. && ix == 0)
+        }) && text.matches('.').count() <= 1
+    }
+
+    /// Whether text is a number within range (blank counts, since it's just not filled in)
+    fn is_valid(&self, text: &str) -> bool {
+        let text = text.trim();
+        text.is_empty()
+            || text.parse::<f32>().is_ok_and(|val| {
+                val >= self.min && val <= self.max && (self.allow_decimal || val.fract() == 0.)
+            })
+    }
+}
 
 pub struct TextInput {
     pub cursor_state: Entity<CursorState>,
@@ -55,38 +135,63 @@ pub struct TextInput {
     pub last_layout: Option<ShapedLine>,
     pub last_bounds: Option<Bounds<Pixels>>,
     is_selecting: bool,
+    numeric: Option<Numeric>,
+    // wheel scrolling not yet turned into a step
+    wheel_delta: Pixels,
+    // (content, selected_range) before each edit, and those undone
+    undo_stack: Vec<(SharedString, Range<usize>)>,
+    redo_stack: Vec<(SharedString, Range<usize>)>,
+    // whether the last edit typed a character, so a run of them undoes as one
+    typing: bool,
     _subscriptions: Vec<Subscription>,
 }
 
 impl TextInput {
-    pub fn new(
-        window: &mut Window,
-        cx: &mut Context<Self>,
-        placeholder: SharedString,
-        tab_index: isize,
-    ) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>, placeholder: SharedString) -> Self {
         let ctrl = cx.ctrl();
-        cx.bind_keys([
-            KeyBinding::new("backspace", Backspace, Some(CONTEXT)),
-            KeyBinding::new("delete", Delete, Some(CONTEXT)),
-            KeyBinding::new("left", Left, Some(CONTEXT)),
-            KeyBinding::new("right", Right, Some(CONTEXT)),
-            KeyBinding::new("shift-left", SelectLeft, Some(CONTEXT)),
-            KeyBinding::new("shift-right", SelectRight, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-a"), SelectAll, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-v"), Paste, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-c"), Copy, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-x"), Cut, Some(CONTEXT)),
-            KeyBinding::new("home", Home, Some(CONTEXT)),
-            KeyBinding::new("end", End, Some(CONTEXT)),
-            KeyBinding::new(
-                &format!("{ctrl}-shift-space"),
-                ShowCharacterPalette,
-                Some(CONTEXT),
-            ),
-        ]);
+        Keymap::bind(
+            cx,
+            CONTEXT,
+            [
+                KeyBinding::new("backspace", Backspace, Some(CONTEXT)),
+                KeyBinding::new(&format!("{WORD}-backspace"), BackspaceWord, Some(CONTEXT)),
+                KeyBinding::new("delete", Delete, Some(CONTEXT)),
+                KeyBinding::new(&format!("{WORD}-delete"), DeleteWord, Some(CONTEXT)),
+                KeyBinding::new("left", Left, Some(CONTEXT)),
+                KeyBinding::new("right", Right, Some(CONTEXT)),
+                KeyBinding::new(&format!("{WORD}-left"), WordLeft, Some(CONTEXT)),
+                KeyBinding::new(&format!("{WORD}-right"), WordRight, Some(CONTEXT)),
+                KeyBinding::new("shift-left", SelectLeft, Some(CONTEXT)),
+                KeyBinding::new("shift-right", SelectRight, Some(CONTEXT)),
+                KeyBinding::new(&format!("{WORD}-shift-left"), SelectWordLeft, Some(CONTEXT)),
+                KeyBinding::new(
+                    &format!("{WORD}-shift-right"),
+                    SelectWordRight,
+                    Some(CONTEXT),
+                ),
+                KeyBinding::new("shift-home", SelectHome, Some(CONTEXT)),
+                KeyBinding::new("shift-end", SelectEnd, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-a"), SelectAll, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-z"), Undo, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-z"), Redo, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-v"), Paste, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-c"), Copy, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-x"), Cut, Some(CONTEXT)),
+                KeyBinding::new("up", StepUp, Some(CONTEXT)),
+                KeyBinding::new("down", StepDown, Some(CONTEXT)),
+                KeyBinding::new("shift-up", StepUpFine, Some(CONTEXT)),
+                KeyBinding::new("shift-down", StepDownFine, Some(CONTEXT)),
+                KeyBinding::new("home", Home, Some(CONTEXT)),
+                KeyBinding::new("end", End, Some(CONTEXT)),
+                KeyBinding::new(
+                    &format!("{ctrl}-shift-space"),
+                    ShowCharacterPalette,
+                    Some(CONTEXT),
+                ),
+            ],
+        );
 
-        let focus_handle = cx.focus_handle().tab_index(tab_index).tab_stop(true);
+        let focus_handle = cx.focus_handle().tab_stop(true);
         cx.on_focus(&focus_handle, window, Self::on_focus).detach();
         cx.on_blur(&focus_handle, window, Self::on_blur).detach();
         let cursor_state = cx.new(|_| CursorState::default());
@@ -103,6 +208,11 @@ impl TextInput {
             last_layout: None,
             last_bounds: None,
             is_selecting: false,
+            numeric: None,
+            wheel_delta: px(0.),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            typing: false,
             _subscriptions: vec![
                 cx.observe(&cursor_state, |_, _, cx| cx.notify()),
                 cx.observe_window_activation(window, |input, window, cx| {
@@ -121,6 +231,33 @@ impl TextInput {
         }
     }
 
+    /// Only accept numbers, marking any outside of numeric's range as invalid
+    pub fn numeric(mut self, numeric: Numeric) -> Self {
+        self.numeric = Some(numeric);
+        self
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.numeric
+            .is_none_or(|numeric| numeric.is_valid(&self.content))
+    }
+
+    // content with range replaced by new_text, or None if numeric rejects the result
+    fn replaced(&self, range: &Range<usize>, new_text: &str) -> Option<SharedString> {
+        let content = self
+            .content
+            .get(0..range.start)
+            .unwrap_or(&self.content[0..])
+            .to_owned()
+            + new_text
+            + self.content.get(range.end..).unwrap_or(&self.content[0..]);
+
+        match self.numeric {
+            Some(numeric) if !numeric.accepts(&content) => None,
+            _ => Some(content.into()),
+        }
+    }
+
     pub fn focus(&self, window: &mut Window) {
         self.focus_handle.focus(window)
     }
@@ -179,6 +316,16 @@ impl TextInput {
         }
     }
 
+    fn word_left(&mut self, _: &WordLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.pause_blink(cx);
+        self.move_to(self.previous_word_boundary(self.cursor_offset()), cx);
+    }
+
+    fn word_right(&mut self, _: &WordRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.pause_blink(cx);
+        self.move_to(self.next_word_boundary(self.cursor_offset()), cx);
+    }
+
     fn select_left(&mut self, _: &SelectLeft, _: &mut Window, cx: &mut Context<Self>) {
         self.select_to(self.previous_boundary(self.cursor_offset()), cx);
     }
@@ -187,6 +334,22 @@ impl TextInput {
         self.select_to(self.next_boundary(self.cursor_offset()), cx);
     }
 
+    fn select_word_left(&mut self, _: &SelectWordLeft, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.previous_word_boundary(self.cursor_offset()), cx);
+    }
+
+    fn select_word_right(&mut self, _: &SelectWordRight, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.next_word_boundary(self.cursor_offset()), cx);
+    }
+
+    fn select_home(&mut self, _: &SelectHome, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(0, cx);
+    }
+
+    fn select_end(&mut self, _: &SelectEnd, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_to(self.content.len(), cx);
+    }
+
     fn select_all(&mut self, _: &SelectAll, _: &mut Window, cx: &mut Context<Self>) {
         self.move_to(0, cx);
         self.select_to(self.content.len(), cx)
@@ -252,10 +415,144 @@ impl TextInput {
         self.pause_blink(cx);
     }
 
+    fn backspace_word(&mut self, _: &BackspaceWord, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.previous_word_boundary(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", window, cx);
+        self.pause_blink(cx);
+    }
+
+    fn delete_word(&mut self, _: &DeleteWord, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_range.is_empty() {
+            self.select_to(self.next_word_boundary(self.cursor_offset()), cx)
+        }
+        self.replace_text_in_range(None, "", window, cx);
+        self.pause_blink(cx);
+    }
+
+    /// Save the current state before an edit so it can be undone
+    fn record(&mut self) {
+        self.undo_stack
+            .push((self.content.clone(), self.selected_range.clone()));
+        if self.undo_stack.len() > MAX_HISTORY {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    // with nothing left to undo/redo in the field, let the parent (e.g., Table) handle it
+    fn undo(&mut self, _: &Undo, _: &mut Window, cx: &mut Context<Self>) {
+        let Some((content, selected_range)) = self.undo_stack.pop() else {
+            cx.propagate();
+            return;
+        };
+        self.redo_stack
+            .push((self.content.clone(), self.selected_range.clone()));
+        self.restore(content, selected_range, cx);
+    }
+
+    fn redo(&mut self, _: &Redo, _: &mut Window, cx: &mut Context<Self>) {
+        let Some((content, selected_range)) = self.redo_stack.pop() else {
+            cx.propagate();
+            return;
+        };
+        self.undo_stack
+            .push((self.content.clone(), self.selected_range.clone()));
+        self.restore(content, selected_range, cx);
+    }
+
+    fn restore(
+        &mut self,
+        content: SharedString,
+        selected_range: Range<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        self.content = content;
+        self.selected_range = selected_range;
+        self.selection_reversed = false;
+        self.marked_range = None;
+        self.typing = false;
+        self.pause_blink(cx);
+        cx.emit(ContentChanged {});
+        cx.notify();
+    }
+
     fn on_key_down(&mut self, _: &KeyDownEvent, _: &mut Window, cx: &mut Context<Self>) {
         self.pause_blink(cx);
     }
 
+    /// Nudge a numeric input's value by delta, keeping it within range
+    fn step(&mut self, delta: f32, cx: &mut Context<Self>) {
+        let Some(numeric) = self.numeric else {
+            cx.propagate();
+            return;
+        };
+
+        let val = self.content.trim().parse::<f32>().unwrap_or(0.) + delta;
+        // only round away float noise, keeping as many places as the value or step has
+        let place = match numeric.allow_decimal {
+            true => decimals(&self.content).max(decimals(&delta.to_string())),
+            false => 0,
+        };
+        let val = round_to_place(val.clamp(numeric.min, numeric.max), place as f32).unwrap_or(val);
+        self.record();
+        self.typing = false;
+        let content: SharedString = val.to_string().into();
+        self.selected_range = content.len()..content.len();
+        self.content = content;
+        cx.emit(ContentChanged {});
+        cx.notify();
+        self.pause_blink(cx);
+    }
+
+    fn step_up(&mut self, _: &StepUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.step(1., cx);
+    }
+
+    fn step_down(&mut self, _: &StepDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.step(-1., cx);
+    }
+
+    fn step_up_fine(&mut self, _: &StepUpFine, _: &mut Window, cx: &mut Context<Self>) {
+        self.step(0.1, cx);
+    }
+
+    fn step_down_fine(&mut self, _: &StepDownFine, _: &mut Window, cx: &mut Context<Self>) {
+        self.step(-0.1, cx);
+    }
+
+    // only step while focused so scrolling over the field doesn't hijack the list's scrolling
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.numeric.is_none() || !self.is_focused(window) {
+            return;
+        }
+        cx.stop_propagation();
+
+        let delta = event.delta.pixel_delta(WHEEL_STEP);
+        // some platforms turn shift-scrolling horizontal
+        self.wheel_delta += if event.modifiers.shift && delta.y == px(0.) {
+            delta.x
+        } else {
+            delta.y
+        };
+
+        let step = if event.modifiers.shift { 0.1 } else { 1. };
+        while self.wheel_delta >= WHEEL_STEP {
+            self.wheel_delta -= WHEEL_STEP;
+            self.step(step, cx);
+        }
+        while self.wheel_delta <= -WHEEL_STEP {
+            self.wheel_delta += WHEEL_STEP;
+            self.step(-step, cx);
+        }
+    }
+
     fn on_mouse_down(
         &mut self,
         event: &MouseDownEvent,
@@ -303,6 +600,11 @@ impl TextInput {
 
     fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
+            // let the parent (e.g., Table) handle multi-line pastes
+            if text.trim().contains('\n') {
+                cx.propagate();
+                return;
+            }
             self.replace_text_in_range(None, &text.replace("\n", " "), window, cx);
         }
     }
@@ -330,7 +632,21 @@ impl TextInput {
         }
     }
 
+    /// Replace the content outright (e.g., restoring a recipe), which also resets its
+    /// undo history
+    pub fn set_content(&mut self, content: SharedString, cx: &mut Context<Self>) {
+        self.selected_range = content.len()..content.len();
+        self.marked_range = None;
+        self.content = content;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.typing = false;
+        cx.emit(ContentChanged {});
+        cx.notify();
+    }
+
     fn move_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.typing = false;
         self.selected_range = offset..offset;
         self.pause_blink(cx);
 
@@ -364,6 +680,7 @@ impl TextInput {
     }
 
     fn select_to(&mut self, offset: usize, cx: &mut Context<Self>) {
+        self.typing = false;
         if self.selection_reversed {
             self.selected_range.start = offset
         } else {
@@ -428,9 +745,32 @@ impl TextInput {
             .find_map(|(idx, _)| (idx > offset).then_some(idx))
             .unwrap_or(self.content.len())
     }
+
+    // start of the word before offset, skipping any whitespace in between
+    fn previous_word_boundary(&self, offset: usize) -> usize {
+        self.content
+            .split_word_bound_indices()
+            .rev()
+            .find_map(|(idx, word)| (idx < offset && !word.trim().is_empty()).then_some(idx))
+            .unwrap_or(0)
+    }
+
+    // end of the word after offset, skipping any whitespace in between
+    fn next_word_boundary(&self, offset: usize) -> usize {
+        self.content
+            .split_word_bound_indices()
+            .find_map(|(idx, word)| {
+                (idx + word.len() > offset && !word.trim().is_empty()).then_some(idx + word.len())
+            })
+            .unwrap_or(self.content.len())
+    }
 }
 
+/// Emitted whenever the content changes, whether edited or set
+pub struct ContentChanged {}
+
 impl EventEmitter<InputEvent> for TextInput {}
+impl EventEmitter<ContentChanged> for TextInput {}
 
 impl EntityInputHandler for TextInput {
     fn text_for_range(
@@ -489,16 +829,19 @@ impl EntityInputHandler for TextInput {
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
 
-        self.content = (self
-            .content
-            .get(0..range.start)
-            .unwrap_or(&self.content[0..])
-            .to_owned()
-            + new_text
-            + self.content.get(range.end..).unwrap_or(&self.content[0..]))
-        .into();
+        let Some(content) = self.replaced(&range, new_text) else {
+            return;
+        };
+        let typing =
+            range.is_empty() && new_text.chars().count() == 1 && !new_text.trim().is_empty();
+        if !(typing && self.typing) {
+            self.record();
+        }
+        self.typing = typing;
+        self.content = content;
         self.selected_range = range.start + new_text.len()..range.start + new_text.len();
         self.marked_range.take();
+        cx.emit(ContentChanged {});
         cx.notify();
     }
 
@@ -516,14 +859,15 @@ impl EntityInputHandler for TextInput {
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
 
-        self.content = (self
-            .content
-            .get(0..range.start)
-            .unwrap_or(&self.content[0..])
-            .to_owned()
-            + new_text
-            + self.content.get(range.end..).unwrap_or(&self.content[0..]))
-        .into();
+        let Some(content) = self.replaced(&range, new_text) else {
+            return;
+        };
+        // composing text (e.g., with an IME) undoes as a whole
+        if self.marked_range.is_none() {
+            self.record();
+        }
+        self.typing = false;
+        self.content = content;
         self.marked_range = Some(range.start..range.start + new_text.len());
         self.selected_range = new_selected_range_utf16
             .as_ref()
@@ -531,6 +875,7 @@ impl EntityInputHandler for TextInput {
             .map(|new_range| new_range.start + range.start..new_range.end + range.end)
             .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
 
+        cx.emit(ContentChanged {});
         cx.notify();
     }
 
@@ -578,19 +923,34 @@ impl Render for TextInput {
             .track_focus(&self.focus_handle(cx))
             .cursor(CursorStyle::IBeam)
             .on_action(cx.listener(Self::backspace))
+            .on_action(cx.listener(Self::backspace_word))
             .on_action(cx.listener(Self::delete))
+            .on_action(cx.listener(Self::delete_word))
             .on_action(cx.listener(Self::left))
             .on_action(cx.listener(Self::right))
+            .on_action(cx.listener(Self::word_left))
+            .on_action(cx.listener(Self::word_right))
             .on_action(cx.listener(Self::select_left))
             .on_action(cx.listener(Self::select_right))
+            .on_action(cx.listener(Self::select_word_left))
+            .on_action(cx.listener(Self::select_word_right))
+            .on_action(cx.listener(Self::select_home))
+            .on_action(cx.listener(Self::select_end))
             .on_action(cx.listener(Self::select_all))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
             .on_action(cx.listener(Self::home))
             .on_action(cx.listener(Self::end))
             .on_action(cx.listener(Self::show_character_palette))
             .on_action(cx.listener(Self::paste))
             .on_action(cx.listener(Self::cut))
             .on_action(cx.listener(Self::copy))
+            .on_action(cx.listener(Self::step_up))
+            .on_action(cx.listener(Self::step_down))
+            .on_action(cx.listener(Self::step_up_fine))
+            .on_action(cx.listener(Self::step_down_fine))
             .on_key_down(cx.listener(Self::on_key_down))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
             .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
             .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
             .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
@@ -600,8 +960,11 @@ impl Render for TextInput {
                 div()
                     .h(px(30. + 4. * 2.))
                     .w(px(120. + 4. * 2.))
-                    .p(px(4.))
+                    .p(px(3.))
                     .bg(cx.theme().background)
+                    .border_1()
+                    .border_color(cx.theme().background)
+                    .when(!self.is_valid(), |this| this.border_color(cx.theme().error))
                     .rounded_md()
                     .child(TextElement { input: cx.entity() }),
             )
@@ -613,3 +976,118 @@ impl Focusable for TextInput {
         self.focus_handle.clone()
     }
 }
+
+// how many digits follow the decimal point in a number's text
+fn decimals(text: &str) -> usize {
+    text.trim()
+        .split_once('.')
+        .map_or(0, |(_, fract)| fract.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::util::{ctrl::Ctrl, theme::Theme};
+
+    use super::*;
+    use gpui::{ScrollDelta, TestAppContext, VisualTestContext};
+
+    #[gpui::test]
+    fn test_text_input_backspace_word(cx: &mut TestAppContext) {
+        let (input, cx) = setup_text_input(cx);
+        let mut result = SharedString::from("");
+
+        cx.simulate_keystrokes(&format!("a b c space d e f {WORD}-backspace"));
+        input.update(cx, |input, _cx| result = input.content.clone());
+
+        assert_eq!(SharedString::from("abc "), result);
+    }
+
+    #[gpui::test]
+    fn test_text_input_word_left_select_end(cx: &mut TestAppContext) {
+        let (input, cx) = setup_text_input(cx);
+        let mut result = SharedString::from("");
+
+        cx.simulate_keystrokes(&format!("a b c space d e f {WORD}-left {WORD}-left right"));
+        cx.simulate_keystrokes("shift-end backspace");
+        input.update(cx, |input, _cx| result = input.content.clone());
+
+        assert_eq!(SharedString::from("a"), result);
+    }
+
+    #[gpui::test]
+    fn test_text_input_undo_redo(cx: &mut TestAppContext) {
+        let (input, cx) = setup_text_input(cx);
+        let ctrl = cx.update(|_window, cx| cx.ctrl());
+        let mut result = Vec::new();
+
+        cx.simulate_keystrokes("a b space c");
+        for keystrokes in ["z", "z", "shift-z"] {
+            cx.simulate_keystrokes(&format!("{ctrl}-{keystrokes}"));
+            input.update(cx, |input, _cx| result.push(input.content.clone()));
+        }
+
+        assert_eq!(
+            vec![
+                SharedString::from("ab "),
+                SharedString::from("ab"),
+                SharedString::from("ab ")
+            ],
+            result
+        );
+    }
+
+    #[gpui::test]
+    fn test_text_input_step_keeps_places(cx: &mut TestAppContext) {
+        let (input, cx) = setup_text_input(cx);
+        let mut result = vec![];
+
+        input.update(cx, |input, _cx| input.numeric = Some(Numeric::default()));
+        cx.simulate_keystrokes("1 . 2 5 up");
+        input.update(cx, |input, _cx| result.push(input.content.clone()));
+        cx.simulate_keystrokes("shift-up shift-up shift-up");
+        input.update(cx, |input, _cx| result.push(input.content.clone()));
+        cx.simulate_keystrokes("backspace backspace backspace backspace 2 shift-up");
+        input.update(cx, |input, _cx| result.push(input.content.clone()));
+
+        assert_eq!(
+            vec![
+                SharedString::from("2.25"),
+                SharedString::from("2.55"),
+                SharedString::from("2.1")
+            ],
+            result
+        );
+    }
+
+    #[gpui::test]
+    fn test_text_input_wheel_when_focused(cx: &mut TestAppContext) {
+        let (input, cx) = setup_text_input(cx);
+        let mut result = vec![];
+        let scroll = ScrollWheelEvent {
+            position: point(px(10.), px(10.)),
+            delta: ScrollDelta::Pixels(point(px(0.), WHEEL_STEP * 2.)),
+            ..Default::default()
+        };
+
+        input.update(cx, |input, _cx| input.numeric = Some(Numeric::default()));
+        cx.simulate_keystrokes("3");
+        cx.simulate_event(scroll.clone());
+        input.update(cx, |input, _cx| result.push(input.content.clone()));
+        cx.update(|window, _cx| window.blur());
+        cx.simulate_event(scroll);
+        input.update(cx, |input, _cx| result.push(input.content.clone()));
+
+        assert_eq!(
+            vec![SharedString::from("5"), SharedString::from("5")],
+            result
+        );
+    }
+
+    fn setup_text_input(cx: &mut TestAppContext) -> (Entity<TextInput>, &mut VisualTestContext) {
+        Theme::test(cx);
+        cx.update(Ctrl::set);
+        let (input, cx) = cx.add_window_view(|window, cx| TextInput::new(window, cx, "".into()));
+        cx.focus(&input);
+        (input, cx)
+    }
+}
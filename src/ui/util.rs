@@ -4,6 +4,11 @@
 pub mod app_menu;
 pub mod assets;
 pub mod ctrl;
+pub mod focus;
+pub mod fuzzy;
+pub mod keymap;
+pub mod session;
+pub mod state;
 pub mod str;
 pub mod theme;
 pub mod window;
@@ -1,47 +1,131 @@
 // SPDX-FileCopyrightText: Camden Boren
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use crate::ui::{About, CloseWindow, Copy, Cut, Hide, Minimize, NewWindow, Paste, Quit, SelectAll};
-use gpui::{Menu, MenuItem, OsAction, SystemMenuType};
+use crate::ui::{
+    About, CloseTab, CloseWindow, Copy, Cut, Hide, Minimize, NewTab, NewWindow, NextTab, Paste,
+    PrevTab, Quit, SelectAll,
+    view::table::data_table::{
+        Clear, CopyCsv, CopyMarkdown, CopyText, Redo, SaveCsv, SaveMarkdown, SaveText, Undo,
+    },
+};
+use gpui::{Action, Menu, MenuItem, OsAction, SystemMenuType};
+
+// what each command is called, in the app menu and the command palette alike
+const LABELS: &[(&str, &str)] = &[
+    ("table::Add", "Add Ingredient"),
+    ("table::Clear", "Clear Recipe"),
+    ("table::CopyCsv", "Copy as CSV"),
+    ("table::CopyMarkdown", "Copy as Markdown"),
+    ("table::CopyText", "Copy as Text"),
+    ("table::Delete", "Delete Last Ingredient"),
+    ("table::Duplicate", "Duplicate Ingredient"),
+    ("table::InsertAbove", "Insert Ingredient Above"),
+    ("table::InsertBelow", "Insert Ingredient Below"),
+    ("table::MoveDown", "Move Ingredient Down"),
+    ("table::MoveUp", "Move Ingredient Up"),
+    ("table::Redo", "Redo"),
+    ("table::RemoveKey", "Remove Ingredient"),
+    ("table::SaveCsv", "Save as CSV…"),
+    ("table::SaveMarkdown", "Save as Markdown…"),
+    ("table::SaveText", "Save as Text…"),
+    ("table::Undo", "Undo"),
+    ("text_input::Copy", "Copy"),
+    ("text_input::Cut", "Cut"),
+    ("text_input::Paste", "Paste"),
+    ("text_input::SelectAll", "Select All"),
+    ("ui::About", "About alc-calc…"),
+    ("ui::CloseTab", "Close Tab"),
+    ("ui::CloseWindow", "Close Window"),
+    ("ui::Hide", "Hide alc-calc"),
+    ("ui::Minimize", "Minimize"),
+    ("ui::NewTab", "New Tab"),
+    ("ui::NewWindow", "New Window"),
+    ("ui::NextTab", "Show Next Tab"),
+    ("ui::PrevTab", "Show Previous Tab"),
+    ("ui::Quit", "Quit alc-calc"),
+    ("ui::Toggle", "Toggle Theme Menu"),
+    ("ui::ToggleHelp", "Toggle Keybindings Help"),
+];
+
+/// The name of the action named name (e.g., table::CopyCsv is "Copy as CSV"), as the
+/// app menu and the command palette show it
+pub fn label(name: &str) -> Option<&'static str> {
+    LABELS
+        .iter()
+        .find(|(action, _)| *action == name)
+        .map(|(_, label)| *label)
+}
+
+fn item(action: impl Action) -> MenuItem {
+    MenuItem::action(label(action.name()).unwrap_or_default(), action)
+}
+
+fn os_item(action: impl Action, os_action: OsAction) -> MenuItem {
+    MenuItem::os_action(label(action.name()).unwrap_or_default(), action, os_action)
+}
 
 pub fn app_menu() -> Vec<Menu> {
     vec![
         Menu {
             name: "alc-calc".into(),
             items: vec![
-                MenuItem::action("About alc-calc…", About),
+                item(About),
                 MenuItem::Separator,
                 MenuItem::os_submenu("Services", SystemMenuType::Services),
                 MenuItem::Separator,
-                MenuItem::action("Hide alc-calc", Hide),
+                item(Hide),
                 MenuItem::Separator,
-                MenuItem::action("Quit alc-calc", Quit),
+                item(Quit),
             ],
         },
         Menu {
             name: "File".into(),
             items: vec![
-                MenuItem::action("New Window", NewWindow),
+                item(NewWindow),
+                item(NewTab),
+                item(Clear),
+                MenuItem::Separator,
+                MenuItem::submenu(Menu {
+                    name: "Export".into(),
+                    items: vec![
+                        item(CopyCsv),
+                        item(CopyMarkdown),
+                        item(CopyText),
+                        MenuItem::Separator,
+                        item(SaveCsv),
+                        item(SaveMarkdown),
+                        item(SaveText),
+                    ],
+                }),
                 MenuItem::Separator,
-                MenuItem::action("Close Window", CloseWindow),
+                item(CloseTab),
+                item(CloseWindow),
             ],
         },
         Menu {
             name: "Edit".into(),
             items: vec![
-                MenuItem::os_action("Cut", Cut, OsAction::Cut),
-                MenuItem::os_action("Copy", Copy, OsAction::Copy),
-                MenuItem::os_action("Paste", Paste, OsAction::Paste),
-                MenuItem::os_action("Select All", SelectAll, OsAction::SelectAll),
+                os_item(Undo, OsAction::Undo),
+                os_item(Redo, OsAction::Redo),
+                MenuItem::Separator,
+                os_item(Cut, OsAction::Cut),
+                os_item(Copy, OsAction::Copy),
+                os_item(Paste, OsAction::Paste),
+                os_item(SelectAll, OsAction::SelectAll),
             ],
         },
         Menu {
             name: "Window".into(),
-            items: vec![MenuItem::action("Minimize", Minimize)],
+            items: vec![
+                item(Minimize),
+                MenuItem::Separator,
+                item(NextTab),
+                item(PrevTab),
+            ],
         },
     ]
 }
 
 pub fn app_dock_menu() -> Vec<MenuItem> {
-    vec![MenuItem::action("New Window", NewWindow)]
+    vec![item(NewWindow)]
 }
@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use gpui::{FocusHandle, Window};
+
+/// Tab order over a view's fields, derived from how they're currently laid out (left to
+/// right, then top to bottom) rather than from indices fixed when they were created
+///
+/// *Note that this also covers fields the window doesn't know about, such as those in
+/// rows scrolled out of a list*
+pub struct FocusOrder {
+    // each field along with the row it's in
+    fields: Vec<(usize, FocusHandle)>,
+}
+
+impl FocusOrder {
+    pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = FocusHandle>,
+    {
+        Self {
+            fields: rows
+                .into_iter()
+                .enumerate()
+                .flat_map(|(row, fields)| fields.into_iter().map(move |field| (row, field)))
+                .collect(),
+        }
+    }
+
+    /// The field (and its row) delta steps from the focused one, or None if none of them
+    /// are focused or the step would leave the order
+    pub fn step(&self, delta: isize, window: &Window) -> Option<(usize, &FocusHandle)> {
+        self.fields
+            .iter()
+            .position(|(_, field)| field.is_focused(window))
+            .and_then(|ix| ix.checked_add_signed(delta))
+            .and_then(|ix| self.fields.get(ix))
+            .map(|(row, field)| (*row, field))
+    }
+}
@@ -0,0 +1,71 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/// Score how well query's characters appear, in order, within candidate (ignoring case
+/// and whitespace in query), favoring matches at word starts and runs of consecutive
+/// characters. Returns None if not all of query's characters appear
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<isize> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut matched = 0;
+    let mut prev: Option<usize> = None;
+
+    for (ix, c) in candidate.iter().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if *c != query[matched] {
+            continue;
+        }
+
+        score += 1;
+        if ix == 0 || candidate[ix - 1] == ' ' {
+            score += 3;
+        }
+        match prev {
+            Some(prev) if prev + 1 == ix => score += 2,
+            Some(prev) => score -= (ix - prev - 1) as isize,
+            None => score -= ix as isize,
+        }
+        prev = Some(ix);
+        matched += 1;
+    }
+
+    (matched == query.len()).then_some(score)
+}
+
+/// Indices of the candidates matching query, best first (ties keep their order)
+pub fn fuzzy_filter<S: AsRef<str>>(query: &str, candidates: &[S]) -> Vec<usize> {
+    let mut matches: Vec<(usize, isize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(ix, candidate)| fuzzy_score(query, candidate.as_ref()).map(|s| (ix, s)))
+        .collect();
+    matches.sort_by_key(|(_, score)| -score);
+    matches.into_iter().map(|(ix, _)| ix).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("galc", "Grain Alcohol").is_some());
+        assert!(fuzzy_score("gz", "Grain Alcohol").is_none());
+        assert!(fuzzy_score("rum", "Rum") > fuzzy_score("rum", "Grain Rum Mix"));
+    }
+
+    #[test]
+    fn test_fuzzy_filter() {
+        let candidates = ["Malt Beer", "Beer", "Baileys"];
+
+        assert_eq!(fuzzy_filter("beer", &candidates), vec![1, 0]);
+        assert_eq!(fuzzy_filter("", &candidates), vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,277 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ui::comp::toast::{ToastVariant, toast};
+use gpui::{
+    Action, App, DummyKeyboardMapper, Global, KeyBinding, KeyBindingContextPredicate, KeyContext,
+    SharedString, Window,
+};
+use std::collections::{BTreeMap, HashSet};
+
+/// The user's keybindings, read from `keymap.toml` in the config dir, which take the
+/// place of the defaults each view binds
+///
+/// Each table is a context (in the form of a KeyBinding's context predicate) mapping
+/// action names to keystrokes, e.g.
+///
+/// ```toml
+/// [Table]
+/// "table::Add" = "ctrl-shift-i"
+///
+/// ["Table && navigating"]
+/// "table::EditCell" = "space"
+/// ```
+#[derive(Default)]
+pub struct Keymap {
+    user: Vec<KeyBinding>,
+    // contexts whose defaults have already been bound
+    bound: HashSet<&'static str>,
+}
+
+impl Global for Keymap {}
+
+type Table = BTreeMap<String, BTreeMap<String, String>>;
+
+impl Keymap {
+    /// Read and bind the user's keybindings (unless another window already has),
+    /// reporting any problems with them through toast
+    pub fn set(cx: &mut App) {
+        if cx.has_global::<Keymap>() {
+            return;
+        }
+
+        // prevents fs access on tests
+        #[cfg(not(test))]
+        let keymap_content = Keymap::read();
+        #[cfg(test)]
+        let keymap_content: Option<String> = None;
+
+        let user = match keymap_content {
+            Some(keymap_content) => {
+                let (user, errors) = Keymap::parse(cx, &keymap_content);
+                errors
+                    .iter()
+                    .for_each(|error| toast(cx, ToastVariant::Error, error));
+                user
+            }
+            None => vec![],
+        };
+        cx.bind_keys(user.clone());
+        cx.set_global(Keymap {
+            user,
+            bound: HashSet::new(),
+        });
+    }
+
+    /// Bind context's default keybindings the first time it's created, leaving out those
+    /// the user has rebound in the same context (by action or by keystrokes)
+    pub fn bind(
+        cx: &mut App,
+        context: &'static str,
+        defaults: impl IntoIterator<Item = KeyBinding>,
+    ) {
+        let keymap = cx.default_global::<Keymap>();
+        if !keymap.bound.insert(context) {
+            return;
+        }
+
+        let defaults: Vec<KeyBinding> = defaults
+            .into_iter()
+            .filter(|default| !keymap.overrides(default))
+            .collect();
+        cx.bind_keys(defaults);
+    }
+
+    /// The keystrokes of the binding that runs action in context (e.g., "Table"), to hint
+    /// at it in a tooltip or menu
+    pub fn keybind(window: &Window, action: &dyn Action, context: &str) -> Option<SharedString> {
+        let context = KeyContext::parse(context).ok()?;
+        window
+            .highest_precedence_binding_for_action_in_context(action, context)
+            .map(|binding| Keymap::keystrokes(&binding))
+    }
+
+    /// binding's keystrokes, separated by spaces (e.g., "ctrl-k ctrl-t")
+    pub fn keystrokes(binding: &KeyBinding) -> SharedString {
+        binding
+            .keystrokes()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ")
+            .into()
+    }
+
+    fn overrides(&self, default: &KeyBinding) -> bool {
+        self.user.iter().any(|user| {
+            (user.action().name() == default.action().name()
+                && user.predicate() == default.predicate())
+                || Keymap::conflicts(user, default)
+        })
+    }
+
+    fn conflicts(a: &KeyBinding, b: &KeyBinding) -> bool {
+        a.keystrokes() == b.keystrokes() && a.predicate() == b.predicate()
+    }
+
+    // unused on tests, which don't read from the fs
+    #[allow(dead_code)]
+    fn read() -> Option<String> {
+        let file_path = dirs::config_dir()
+            .unwrap_or_default()
+            .join("alc-calc")
+            .join("keymap.toml");
+        std::fs::read_to_string(file_path).ok()
+    }
+
+    /// Build keymap_content's keybindings, skipping (and describing) any that can't be
+    /// built or that conflict with one another
+    fn parse(cx: &App, keymap_content: &str) -> (Vec<KeyBinding>, Vec<String>) {
+        let table: Table = match toml::from_str(keymap_content) {
+            Ok(table) => table,
+            Err(_) => {
+                let error = "Failed to deserialize keymap. Using default keybindings";
+                return (vec![], vec![error.into()]);
+            }
+        };
+
+        let mut bindings: Vec<KeyBinding> = vec![];
+        let mut errors = vec![];
+        for (context, actions) in table {
+            let Ok(predicate) = KeyBindingContextPredicate::parse(&context) else {
+                errors.push(format!("Invalid context \"{context}\" in keymap"));
+                continue;
+            };
+            let predicate = Some(predicate.into());
+
+            for (name, keystrokes) in actions {
+                let Ok(action) = cx.build_action(&name, None) else {
+                    errors.push(format!("Unknown action \"{name}\" in keymap"));
+                    continue;
+                };
+                let Ok(binding) = KeyBinding::load(
+                    &keystrokes,
+                    action,
+                    predicate.clone(),
+                    false,
+                    None,
+                    &DummyKeyboardMapper,
+                ) else {
+                    errors.push(format!("Invalid keystrokes \"{keystrokes}\" for {name}"));
+                    continue;
+                };
+
+                match bindings
+                    .iter()
+                    .find(|other| Keymap::conflicts(other, &binding))
+                {
+                    Some(other) => errors.push(format!(
+                        "{name} and {} are both bound to \"{keystrokes}\" in {context}",
+                        other.action().name()
+                    )),
+                    None => bindings.push(binding),
+                }
+            }
+        }
+
+        (bindings, errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{Tab, Toggle};
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    fn test_parse(cx: &mut TestAppContext) {
+        let keymap_content = "[UI]\n\"ui::Toggle\" = \"ctrl-y\"\n";
+        let mut result = (vec![], vec![]);
+
+        cx.update(|cx| result = Keymap::parse(cx, keymap_content));
+        let (bindings, errors) = result;
+
+        assert_eq!(1, bindings.len());
+        assert_eq!("ui::Toggle", bindings[0].action().name());
+        assert!(errors.is_empty());
+    }
+
+    #[gpui::test]
+    fn test_parse_errors(cx: &mut TestAppContext) {
+        let keymap_content = "[UI]
+\"ui::Toggle\" = \"ctrl-y\"
+\"ui::Tab\" = \"ctrl-y\"
+\"ui::Nothing\" = \"ctrl-u\"
+\"ui::Quit\" = \"hyper-q\"
+
+[\"UI &&\"]
+\"ui::Quit\" = \"ctrl-q\"
+";
+        let mut result = (vec![], vec![]);
+
+        cx.update(|cx| result = Keymap::parse(cx, keymap_content));
+        let (bindings, errors) = result;
+
+        assert_eq!(1, bindings.len());
+        assert_eq!(
+            vec![
+                "Unknown action \"ui::Nothing\" in keymap",
+                "Invalid keystrokes \"hyper-q\" for ui::Quit",
+                "ui::Toggle and ui::Tab are both bound to \"ctrl-y\" in UI",
+                "Invalid context \"UI &&\" in keymap",
+            ],
+            errors
+        );
+    }
+
+    #[gpui::test]
+    fn test_overrides(cx: &mut TestAppContext) {
+        let keymap_content = "[UI]\n\"ui::Toggle\" = \"ctrl-y\"\n";
+        let mut keymap = Keymap::default();
+
+        cx.update(|cx| keymap.user = Keymap::parse(cx, keymap_content).0);
+
+        assert!(keymap.overrides(&KeyBinding::new("ctrl-t", Toggle, Some("UI"))));
+        assert!(keymap.overrides(&KeyBinding::new("ctrl-y", Tab, Some("UI"))));
+        assert!(!keymap.overrides(&KeyBinding::new("ctrl-y", Tab, Some("Table"))));
+        assert!(!keymap.overrides(&KeyBinding::new("tab", Tab, Some("UI"))));
+    }
+
+    #[gpui::test]
+    fn test_keybind(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let default = KeyBinding::new("ctrl-t", Toggle, Some("UI"));
+        let user = KeyBinding::new("ctrl-k ctrl-t", Toggle, Some("UI"));
+        let mut keybinds = vec![];
+
+        cx.update(|window, cx| {
+            cx.bind_keys([default.clone()]);
+            keybinds.push(Keymap::keybind(window, &Toggle, "UI"));
+            cx.bind_keys([user.clone()]);
+            keybinds.push(Keymap::keybind(window, &Toggle, "UI"));
+            keybinds.push(Keymap::keybind(window, &Toggle, "Table"));
+        });
+
+        // the most recently bound wins
+        assert_eq!(
+            vec![
+                Some(Keymap::keystrokes(&default)),
+                Some(Keymap::keystrokes(&user)),
+                None
+            ],
+            keybinds
+        );
+    }
+
+    #[gpui::test]
+    fn test_overrides_other_context(cx: &mut TestAppContext) {
+        let keymap_content = "[UI]\n\"ui::Toggle\" = \"ctrl-y\"\n";
+        let mut keymap = Keymap::default();
+
+        cx.update(|cx| keymap.user = Keymap::parse(cx, keymap_content).0);
+
+        assert!(!keymap.overrides(&KeyBinding::new("ctrl-t", Toggle, Some("Table"))));
+        assert!(!keymap.overrides(&KeyBinding::new("ctrl-t", Toggle, None)));
+    }
+}
@@ -0,0 +1,164 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ui::{
+    comp::toast::{ToastVariant, toast},
+    view::table::ingredient::IngredientData,
+};
+use gpui::App;
+use serde::{Deserialize, Serialize};
+use std::{fs::write, path::PathBuf};
+
+/// Each tab's recipe, stored in the config dir as `session.toml` so that new windows can
+/// pick up where the last one left off
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Session {
+    #[serde(default)]
+    pub active: usize,
+    pub tabs: Vec<Recipe>,
+}
+
+/// A snapshot of a table's contents
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Recipe {
+    pub units: String,
+    pub ingreds: Vec<RecipeIngredient>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct RecipeIngredient {
+    pub ingred_type: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    pub percentage: String,
+    pub parts: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+}
+
+impl From<&IngredientData> for RecipeIngredient {
+    /// Convert calculation data into field contents, leaving parts blank when 0
+    fn from(data: &IngredientData) -> Self {
+        Self {
+            ingred_type: data.ingred_type.to_string(),
+            label: data.label.to_string(),
+            percentage: data.percentage.to_string(),
+            parts: match data.parts {
+                0. => "".into(),
+                parts => parts.to_string(),
+            },
+            notes: data.notes.to_string(),
+        }
+    }
+}
+
+impl Session {
+    pub fn read(cx: &mut App) -> Option<Session> {
+        let file_path = Session::path().join("session.toml");
+        let session_content = std::fs::read_to_string(file_path).ok()?;
+        Session::deserialize(cx, &session_content)
+    }
+
+    pub fn write(&self, cx: &mut App) {
+        let Some(session_content) = Session::serialize(cx, self) else {
+            return;
+        };
+        let path = Session::path();
+        if std::fs::metadata(&path).is_err() && std::fs::create_dir(&path).is_err() {
+            toast(cx, ToastVariant::Error, "Failed to create config directory");
+            return;
+        }
+        if write(path.join("session.toml"), session_content).is_err() {
+            toast(cx, ToastVariant::Error, "Failed to write to session file");
+        }
+    }
+
+    fn path() -> PathBuf {
+        dirs::config_dir().unwrap_or_default().join("alc-calc")
+    }
+
+    fn deserialize(cx: &mut App, session_content: &str) -> Option<Session> {
+        match toml::from_str(session_content) {
+            Ok(session) => Some(session),
+            Err(_) => {
+                toast(
+                    cx,
+                    ToastVariant::Error,
+                    "Failed to deserialize last session. Starting fresh",
+                );
+                None
+            }
+        }
+    }
+
+    fn serialize(cx: &mut App, session: &Session) -> Option<String> {
+        match toml::to_string(session) {
+            Ok(session_content) => Some(session_content),
+            Err(_) => {
+                toast(cx, ToastVariant::Error, "Failed to serialize session");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    const SESSION: &str = "active = 1
+
+[[tabs]]
+units = \"2\"
+
+[[tabs.ingreds]]
+ingred_type = \"Gin\"
+label = \"Plymouth\"
+percentage = \"40\"
+parts = \"2\"
+
+[[tabs]]
+units = \"\"
+ingreds = []
+";
+
+    fn session() -> Session {
+        Session {
+            active: 1,
+            tabs: vec![
+                Recipe {
+                    units: "2".into(),
+                    ingreds: vec![RecipeIngredient {
+                        ingred_type: "Gin".into(),
+                        label: "Plymouth".into(),
+                        percentage: "40".into(),
+                        parts: "2".into(),
+                        ..Default::default()
+                    }],
+                },
+                Recipe::default(),
+            ],
+        }
+    }
+
+    #[gpui::test]
+    fn test_deserialize_session(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let mut result = None;
+
+        cx.update(|_, cx| result = Session::deserialize(cx, SESSION));
+
+        assert_eq!(result, Some(session()));
+    }
+
+    #[gpui::test]
+    fn test_serialize_session(cx: &mut TestAppContext) {
+        let cx = cx.add_empty_window();
+        let mut result = None;
+
+        cx.update(|_, cx| result = Session::serialize(cx, &session()));
+
+        assert_eq!(result, Some(SESSION.to_string()));
+    }
+}
@@ -0,0 +1,345 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ui::util::{
+    session::Session,
+    theme::{ActiveTheme, Theme, ThemeVariant},
+};
+#[cfg(test)]
+use gpui::TestAppContext;
+use gpui::{App, AppContext, Context, Entity, Global, WindowId};
+#[cfg(test)]
+use std::cell::RefCell;
+use std::{rc::Rc, str::FromStr, time::Duration};
+
+// how long the session has to go unchanged before it's written, so that a burst of
+// changes (e.g., typing) is saved once
+const SAVE_DELAY: u64 = 500;
+
+/// The state shared by every window: each window's tabs and the user's settings
+///
+/// Changes go through its methods, which persist them (through its Store) and notify
+/// observers. Every UI follows its window's tabs here, restoring its tables whenever
+/// they differ, and hands back its tables' recipes whenever they're edited
+pub struct AppState {
+    /// Every open window's tabs, in the order the windows were opened, as saved to disk
+    pub session: Session,
+    pub settings: Settings,
+    // each open window's own tabs
+    windows: Vec<(WindowId, Session)>,
+    // whether the session changed since it was last written
+    unsaved: bool,
+    save_epoch: usize,
+    store: Rc<dyn Store>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub theme: ThemeVariant,
+}
+
+impl AppState {
+    /// Load the last session and settings, unless another window already has
+    ///
+    /// The session is written on quit, and shrinks to the remaining windows' tabs when a
+    /// window closes (unless it was the last one, so that it's there on the next launch)
+    ///
+    /// *Note that this expects the Theme global to be set, as it's the source of the
+    /// theme setting*
+    pub fn set(cx: &mut App) {
+        AppState::set_with(cx, Rc::new(ConfigDir));
+    }
+
+    fn set_with(cx: &mut App, store: Rc<dyn Store>) {
+        if cx.has_global::<GlobalAppState>() {
+            return;
+        }
+
+        let session = store.read_session(cx).unwrap_or_default();
+
+        let settings = Settings {
+            theme: cx.theme().variant.clone(),
+        };
+        let state = cx.new(|cx| {
+            cx.on_app_quit(|this: &mut AppState, cx| {
+                this.flush(cx);
+                async {}
+            })
+            .detach();
+            AppState {
+                session,
+                settings,
+                windows: vec![],
+                unsaved: false,
+                save_epoch: 0,
+                store,
+            }
+        });
+        let weak_state = state.downgrade();
+        cx.on_window_closed(move |cx| {
+            weak_state.update(cx, |state, cx| state.close(cx)).ok();
+        })
+        .detach();
+        cx.set_global(GlobalAppState(state));
+    }
+
+    pub fn global(cx: &App) -> Entity<Self> {
+        cx.global::<GlobalAppState>().0.clone()
+    }
+
+    /// The tabs a newly opened window starts with: the last session's for the first
+    /// window, and none for any others, as their tabs are already open
+    pub fn open(&mut self, window: WindowId) -> Session {
+        let session = match self.windows.is_empty() {
+            true => self.session.clone(),
+            false => Session::default(),
+        };
+        self.windows.push((window, session.clone()));
+        session
+    }
+
+    /// The tabs of window, if it's open
+    pub fn window_session(&self, window: WindowId) -> Option<&Session> {
+        self.windows
+            .iter()
+            .find(|(id, _)| *id == window)
+            .map(|(_, tabs)| tabs)
+    }
+
+    /// Replace window's tabs, saving the session to disk once it's gone unchanged for a
+    /// moment
+    pub fn set_session(&mut self, window: WindowId, session: Session, cx: &mut Context<Self>) {
+        match self.windows.iter_mut().find(|(id, _)| *id == window) {
+            Some((_, tabs)) => *tabs = session,
+            None => self.windows.push((window, session)),
+        }
+        self.merge(cx);
+    }
+
+    // forget the tabs of any windows that have closed, keeping the last window's
+    fn close(&mut self, cx: &mut Context<Self>) {
+        let open = cx
+            .windows()
+            .iter()
+            .map(|w| w.window_id())
+            .collect::<Vec<_>>();
+        if open.is_empty() {
+            self.windows.clear();
+            return;
+        }
+        self.windows.retain(|(id, _)| open.contains(id));
+        self.merge(cx);
+    }
+
+    // join every window's tabs into the session, keeping the first window's active tab
+    fn merge(&mut self, cx: &mut Context<Self>) {
+        let session = Session {
+            active: self.windows.first().map_or(0, |(_, tabs)| tabs.active),
+            tabs: self
+                .windows
+                .iter()
+                .flat_map(|(_, tabs)| tabs.tabs.clone())
+                .collect(),
+        };
+        if self.session == session {
+            return;
+        }
+        self.session = session;
+        self.unsaved = true;
+
+        self.save_epoch += 1;
+        let epoch = self.save_epoch;
+        cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(Duration::from_millis(SAVE_DELAY))
+                .await;
+            this.update(cx, |this, cx| {
+                if epoch == this.save_epoch {
+                    this.flush(cx);
+                }
+            })
+        })
+        .detach();
+        cx.notify();
+    }
+
+    /// Write the session now if it has unsaved changes (e.g., on quit)
+    pub fn flush(&mut self, cx: &mut Context<Self>) {
+        if !self.unsaved {
+            return;
+        }
+        self.unsaved = false;
+        self.store.write_session(&self.session, cx);
+    }
+
+    /// Switch to and save the theme named theme_str, built in or from the themes dir
+    pub fn set_theme(&mut self, theme_str: &str, cx: &mut Context<Self>) {
+        Theme::select(cx, theme_str);
+        self.store.write_theme(theme_str, cx);
+
+        self.settings.theme = ThemeVariant::from_str(theme_str).unwrap_or(ThemeVariant::Dark);
+        cx.notify();
+    }
+
+    #[cfg(test)]
+    pub fn test(cx: &mut TestAppContext) -> Rc<MemoryStore> {
+        let store = Rc::new(MemoryStore::default());
+        cx.update(|cx| AppState::set_with(cx, store.clone()));
+        store
+    }
+}
+
+pub struct GlobalAppState(Entity<AppState>);
+
+impl Global for GlobalAppState {}
+
+/// Where AppState reads the last session from and writes its changes to
+pub trait Store {
+    fn read_session(&self, cx: &mut App) -> Option<Session>;
+    fn write_session(&self, session: &Session, cx: &mut App);
+    fn write_theme(&self, theme_str: &str, cx: &mut App);
+}
+
+/// The config dir's session.toml and config.toml
+pub struct ConfigDir;
+
+impl Store for ConfigDir {
+    fn read_session(&self, cx: &mut App) -> Option<Session> {
+        Session::read(cx)
+    }
+
+    fn write_session(&self, session: &Session, cx: &mut App) {
+        session.write(cx);
+    }
+
+    fn write_theme(&self, theme_str: &str, cx: &mut App) {
+        Theme::write(cx, theme_str);
+    }
+}
+
+/// Keeps whatever's written in memory, so tests don't touch the config dir
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStore {
+    pub session: RefCell<Option<Session>>,
+    pub theme: RefCell<Option<String>>,
+}
+
+#[cfg(test)]
+impl Store for MemoryStore {
+    fn read_session(&self, _cx: &mut App) -> Option<Session> {
+        self.session.borrow().clone()
+    }
+
+    fn write_session(&self, session: &Session, _cx: &mut App) {
+        self.session.replace(Some(session.clone()));
+    }
+
+    fn write_theme(&self, theme_str: &str, _cx: &mut App) {
+        self.theme.replace(Some(theme_str.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::util::session::Recipe;
+    use std::cell::Cell;
+
+    #[gpui::test]
+    fn test_set_session(cx: &mut TestAppContext) {
+        let (state, store) = setup_state(cx);
+        let notified = Rc::new(Cell::new(0));
+        let session = Session {
+            active: 1,
+            tabs: vec![Recipe::default(), Recipe::default()],
+        };
+
+        cx.update(|cx| {
+            let notified = notified.clone();
+            cx.observe(&state, move |_state, _cx| notified.set(notified.get() + 1))
+                .detach();
+        });
+        (0..2).for_each(|_| {
+            state.update(cx, |state, cx| {
+                state.set_session(WindowId::from(1), session.clone(), cx)
+            });
+        });
+        let unsaved = state.read_with(cx, |state, _cx| state.unsaved);
+        cx.executor()
+            .advance_clock(Duration::from_millis(SAVE_DELAY));
+        cx.run_until_parked();
+
+        assert_eq!(1, notified.get());
+        assert!(unsaved);
+        state.read_with(cx, |state, _cx| {
+            assert_eq!(session, state.session);
+            assert!(!state.unsaved);
+        });
+        assert_eq!(Some(session), store.session.take());
+    }
+
+    #[gpui::test]
+    fn test_set_session_windows(cx: &mut TestAppContext) {
+        let (state, _store) = setup_state(cx);
+        let mut opened = vec![];
+        let recipe = |units: &str| Recipe {
+            units: units.into(),
+            ingreds: vec![],
+        };
+
+        state.update(cx, |state, cx| {
+            state.session = Session {
+                active: 0,
+                tabs: vec![recipe("1")],
+            };
+            opened.push(state.open(WindowId::from(1)));
+            opened.push(state.open(WindowId::from(2)));
+            state.set_session(
+                WindowId::from(2),
+                Session {
+                    active: 0,
+                    tabs: vec![recipe("2")],
+                },
+                cx,
+            );
+            state.set_session(
+                WindowId::from(1),
+                Session {
+                    active: 1,
+                    tabs: vec![recipe("1"), recipe("3")],
+                },
+                cx,
+            );
+        });
+
+        assert_eq!(vec![recipe("1")], opened[0].tabs);
+        assert!(opened[1].tabs.is_empty());
+        state.read_with(cx, |state, _cx| {
+            assert_eq!(1, state.session.active);
+            assert_eq!(
+                vec![recipe("1"), recipe("3"), recipe("2")],
+                state.session.tabs
+            );
+        });
+    }
+
+    #[gpui::test]
+    fn test_set_theme(cx: &mut TestAppContext) {
+        let (state, store) = setup_state(cx);
+        let mut theme = ThemeVariant::Light;
+
+        state.update(cx, |state, cx| state.set_theme("RosePineMoon", cx));
+        cx.update(|cx| theme = cx.theme().variant.clone());
+
+        assert_eq!(ThemeVariant::RosePineMoon, theme);
+        state.read_with(cx, |state, _cx| assert_eq!(theme, state.settings.theme));
+        assert_eq!(Some("RosePineMoon".to_string()), store.theme.take());
+    }
+
+    fn setup_state(cx: &mut TestAppContext) -> (Entity<AppState>, Rc<MemoryStore>) {
+        Theme::test(cx);
+        let store = AppState::test(cx);
+        (cx.update(|cx| AppState::global(cx)), store)
+    }
+}
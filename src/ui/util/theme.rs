@@ -3,17 +3,24 @@
 
 // ActiveTheme adapted from https://github.com/zed-industries/zed/blob/main/crates/theme/src/theme.rs
 
-use gpui::{App, Global, Hsla, Rgba, TestAppContext, hsla, rgb, rgba};
-use serde::{Deserialize, Serialize};
+use gpui::{App, Global, Hsla, Rgba, TestAppContext, WindowAppearance, hsla, rgb, rgba};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::{
+    collections::BTreeMap,
     fs::{File, write},
     io::Read,
-    path::PathBuf,
+    ops::Range,
+    path::{Path, PathBuf},
     str::FromStr,
+    time::{Duration, SystemTime},
 };
 use strum_macros::{Display, EnumCount, EnumIter, EnumString};
+use toml::Spanned;
 
-use crate::ui::comp::toast::{ToastVariant, toast};
+use crate::ui::{
+    comp::toast::{ToastVariant, toast},
+    util::state::{AppState, GlobalAppState},
+};
 
 const DEFAULT_THEME: &str = "theme = \"Dark\"\n";
 #[cfg(target_os = "linux")]
@@ -28,6 +35,7 @@ field = \"#1d1d1dff\"
 field_text = \"#e6e6e6e6\"
 cursor = \"#3311ffff\"
 highlight = \"#3311ff30\"
+error = \"#e5484dff\"
 border = \"#646464ff\"
 separator = \"#000000ff\"
 scrollbar = \"#606060ff\"
@@ -51,6 +59,7 @@ field = \"#1d1d1dff\"
 field_text = \"#e6e6e6e6\"
 cursor = \"#3311ffff\"
 highlight = \"#3311ff30\"
+error = \"#e5484dff\"
 border = \"#646464ff\"
 separator = \"#000000ff\"
 scrollbar = \"#606060ff\"
@@ -70,15 +79,31 @@ field = \"#1d1d1dff\"
 field_text = \"#e6e6e6e6\"
 cursor = \"#3311ffff\"
 highlight = \"#3311ff30\"
+error = \"#e5484dff\"
 border = \"#646464ff\"
 separator = \"#000000ff\"
 scrollbar = \"#606060ff\"
 scrollbar_hover = \"#505050ff\"
 ";
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 struct Config {
     theme: ThemeVariant,
+    // what Auto follows in light and dark mode (Light and Dark if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    light_theme: Option<ThemeVariant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dark_theme: Option<ThemeVariant>,
+}
+
+impl Config {
+    fn new(theme: ThemeVariant) -> Self {
+        Self {
+            theme,
+            light_theme: None,
+            dark_theme: None,
+        }
+    }
 }
 
 #[derive(
@@ -91,6 +116,11 @@ pub enum ThemeVariant {
     RosePineMoon,
     SolarizedDark,
     Custom,
+    Auto,
+    // a theme read from the themes dir, by its file's name
+    #[serde(untagged)]
+    #[strum(default)]
+    Named(String),
 }
 
 #[derive(Serialize, Debug, Deserialize, PartialEq)]
@@ -106,6 +136,9 @@ pub struct Theme {
     pub field_text: Hsla,
     pub cursor: Rgba,
     pub highlight: Rgba,
+    // older custom themes predate this, so don't fail to load them over it
+    #[serde(default = "Theme::default_error")]
+    pub error: Rgba,
     pub border: Rgba,
     pub separator: Rgba,
     pub scrollbar: Rgba,
@@ -126,6 +159,17 @@ pub struct Theme {
 
 impl Global for Theme {}
 
+// what the config dir's watcher needs to know of the app's own doings: config.toml's
+// modification time as of the app's last write to it (so that isn't taken for an edit)
+// and the errors last reported
+#[derive(Default)]
+struct Watched {
+    written: Option<SystemTime>,
+    errors: Vec<String>,
+}
+
+impl Global for Watched {}
+
 pub trait ActiveTheme {
     fn theme(&self) -> &Theme;
 }
@@ -140,28 +184,184 @@ impl Theme {
     pub fn set(cx: &mut App) {
         let path = dirs::config_dir().unwrap_or_default().join("alc-calc");
         let config_content = Theme::read(cx, path.clone()).unwrap_or(String::from(DEFAULT_THEME));
-        let theme = match Theme::deserialize(cx, config_content) {
-            ThemeVariant::Dark => Theme::dark(),
-            ThemeVariant::Light => Theme::light(),
-            ThemeVariant::RedDark => Theme::red_dark(),
-            ThemeVariant::RosePineMoon => Theme::rose_pine_moon(),
-            ThemeVariant::SolarizedDark => Theme::solarized_dark(),
-            ThemeVariant::Custom => Theme::read_theme(cx, path).unwrap_or(Theme::custom()),
-        };
+        let (config, mut errors) = Theme::known(Theme::deserialize(cx, config_content), &path);
+        let theme = Theme::build(cx, &config.theme, &config, path, &mut errors);
+        errors
+            .iter()
+            .for_each(|error| toast(cx, ToastVariant::Error, error));
+        cx.default_global::<Watched>().errors = errors;
         cx.set_global(theme);
     }
 
+    /// Show the theme named val without reporting any errors in its file, as previewing
+    /// each theme in turn would otherwise report them again and again
     pub fn preview(cx: &mut App, val: &str) {
+        Theme::apply(cx, val);
+    }
+
+    /// Show the theme named val, reporting any errors in its file
+    pub fn select(cx: &mut App, val: &str) {
+        let errors = Theme::apply(cx, val);
+        errors
+            .iter()
+            .for_each(|error| toast(cx, ToastVariant::Error, error));
+        cx.default_global::<Watched>().errors = errors;
+    }
+
+    fn apply(cx: &mut App, val: &str) -> Vec<String> {
         let path = dirs::config_dir().unwrap_or_default().join("alc-calc");
-        let theme = match ThemeVariant::from_str(val).unwrap_or(ThemeVariant::Dark) {
-            ThemeVariant::Dark => Theme::dark(),
-            ThemeVariant::Light => Theme::light(),
-            ThemeVariant::RedDark => Theme::red_dark(),
-            ThemeVariant::RosePineMoon => Theme::rose_pine_moon(),
-            ThemeVariant::SolarizedDark => Theme::solarized_dark(),
-            ThemeVariant::Custom => Theme::read_theme(cx, path).unwrap_or(Theme::custom()),
-        };
+        let variant = ThemeVariant::from_str(val).unwrap_or(ThemeVariant::Dark);
+        // prevents fs access on tests
+        #[cfg(not(test))]
+        let config = Theme::current_config(&path);
+        #[cfg(test)]
+        let config = Config::new(ThemeVariant::Dark);
+        let mut errors = vec![];
+        let theme = Theme::build(cx, &variant, &config, path, &mut errors);
         cx.set_global(theme);
+        errors
+    }
+
+    /// variant's theme, with Custom read from theme.toml and Auto following the system
+    /// appearance, adding any errors in the theme's file to errors
+    fn build(
+        cx: &mut App,
+        variant: &ThemeVariant,
+        config: &Config,
+        path: PathBuf,
+        errors: &mut Vec<String>,
+    ) -> Theme {
+        let mut theme = match Theme::follow(cx, variant, config) {
+            ThemeVariant::Custom => {
+                // prevents fs access on tests (namely, when Custom is selected in
+                // Theme::preview())
+                #[cfg(not(test))]
+                if std::fs::metadata(path.join("theme.toml")).is_err() {
+                    Theme::write_theme(cx, path.clone());
+                }
+                Theme::read_theme(path, errors).unwrap_or(Theme::custom())
+            }
+            named @ ThemeVariant::Named(_) => {
+                let file = Theme::file(&named).unwrap_or_default();
+                Theme::read_named(&path, &file, errors)
+            }
+            // follow never returns Auto
+            followed => Theme::builtin(&followed).unwrap_or(Theme::dark()),
+        };
+        theme.variant = variant.clone();
+        theme
+    }
+
+    /// variant's theme, if it's one of the themes built into the app
+    fn builtin(variant: &ThemeVariant) -> Option<Theme> {
+        match variant {
+            ThemeVariant::Dark => Some(Theme::dark()),
+            ThemeVariant::Light => Some(Theme::light()),
+            ThemeVariant::RedDark => Some(Theme::red_dark()),
+            ThemeVariant::RosePineMoon => Some(Theme::rose_pine_moon()),
+            ThemeVariant::SolarizedDark => Some(Theme::solarized_dark()),
+            ThemeVariant::Custom | ThemeVariant::Auto | ThemeVariant::Named(_) => None,
+        }
+    }
+
+    /// The file in the config dir variant's theme is read from, if it isn't built in
+    fn file(variant: &ThemeVariant) -> Option<String> {
+        match variant {
+            ThemeVariant::Custom => Some(String::from("theme.toml")),
+            ThemeVariant::Named(name) => Some(format!("themes/{name}.toml")),
+            _ => None,
+        }
+    }
+
+    /// The names of the themes in the themes dir (the stems of its toml files), sorted
+    pub fn named() -> Vec<String> {
+        // prevents fs access on tests
+        #[cfg(not(test))]
+        return Theme::names(&dirs::config_dir().unwrap_or_default().join("alc-calc"));
+        #[cfg(test)]
+        vec![]
+    }
+
+    fn names(path: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(path.join("themes"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|file| {
+                file.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .filter_map(|file| file.file_stem()?.to_str().map(String::from))
+            // a built-in theme's name would select the built-in theme instead
+            .filter(|name| matches!(ThemeVariant::from_str(name), Ok(ThemeVariant::Named(_))))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// config with any theme missing from the themes dir replaced (theme by Dark, and
+    /// light_theme and dark_theme by Auto's defaults), along with an error for each, so
+    /// that a typo isn't taken for a named theme
+    fn known(config: Config, path: &Path) -> (Config, Vec<String>) {
+        Theme::known_in(config, &Theme::names(path))
+    }
+
+    fn known_in(mut config: Config, names: &[String]) -> (Config, Vec<String>) {
+        let unknown = |variant: &ThemeVariant| matches!(variant, ThemeVariant::Named(name) if !names.contains(name));
+        let mut errors = vec![];
+
+        if unknown(&config.theme) {
+            errors.push(format!(
+                "Unknown theme \"{}\" in config.toml: expected a built-in theme or one in \
+                 the themes dir. Defaulting to Dark theme",
+                config.theme
+            ));
+            config.theme = ThemeVariant::Dark;
+        }
+        for (key, variant) in [
+            ("light_theme", &mut config.light_theme),
+            ("dark_theme", &mut config.dark_theme),
+        ] {
+            if let Some(name) = variant.as_ref().filter(|variant| unknown(variant)) {
+                errors.push(format!(
+                    "Unknown theme \"{name}\" for {key} in config.toml: expected a built-in \
+                     theme or one in the themes dir"
+                ));
+                *variant = None;
+            }
+        }
+
+        (config, errors)
+    }
+
+    /// The variant to display for variant, which is itself unless it's Auto
+    fn follow(cx: &App, variant: &ThemeVariant, config: &Config) -> ThemeVariant {
+        if variant != &ThemeVariant::Auto {
+            return variant.clone();
+        }
+
+        let (followed, fallback) = match cx.window_appearance() {
+            WindowAppearance::Light | WindowAppearance::VibrantLight => {
+                (&config.light_theme, ThemeVariant::Light)
+            }
+            WindowAppearance::Dark | WindowAppearance::VibrantDark => {
+                (&config.dark_theme, ThemeVariant::Dark)
+            }
+        };
+        followed
+            .clone()
+            .filter(|variant| variant != &ThemeVariant::Auto)
+            .unwrap_or(fallback)
+    }
+
+    // the config as it is on disk, if it can be read, so that Auto's light and dark themes
+    // are kept when previewing or selecting another theme
+    fn current_config(path: &Path) -> Config {
+        std::fs::read_to_string(path.join("config.toml"))
+            .ok()
+            .and_then(|config_content| toml::from_str(&config_content).ok())
+            .unwrap_or(Config::new(ThemeVariant::Dark))
     }
 
     pub fn global(cx: &App) -> &Theme {
@@ -181,6 +381,7 @@ impl Theme {
             field_text: hsla(0., 0., 0.9, 0.9),
             cursor: rgb(0x3311ff),
             highlight: rgba(0x3311ff30),
+            error: rgb(0xe5484d),
             border: rgba(0x646464ff),
             separator: rgba(0x000000ff),
             scrollbar: rgba(0x606060ff),
@@ -213,6 +414,7 @@ impl Theme {
             field_text: hsla(0., 0., 0.1, 0.9),
             cursor: rgb(0x3311ff),
             highlight: rgba(0x3311ff30),
+            error: rgb(0xcd2b31),
             border: rgba(0x969696ff),
             separator: rgba(0x969696ff),
             scrollbar: rgba(0xc8c8c8ff),
@@ -245,6 +447,7 @@ impl Theme {
             field_text: hsla(0., 0.5, 0.9, 0.9),
             cursor: rgb(0xd12727),
             highlight: rgba(0xd1272730),
+            error: rgb(0xff8a80),
             border: rgba(0x6e2c2fff),
             separator: rgba(0x000000ff),
             scrollbar: rgba(0xa00000ff),
@@ -277,6 +480,7 @@ impl Theme {
             field_text: hsla(0.7, 0.5, 0.9, 0.9),
             cursor: rgb(0x9bced6),
             highlight: rgba(0x9bced630),
+            error: rgb(0xeb6f92),
             border: rgba(0x504c68ff),
             separator: rgba(0x000000ff),
             scrollbar: rgba(0x544e78ff),
@@ -309,6 +513,7 @@ impl Theme {
             field_text: hsla(0.5, 0.5, 0.9, 0.9),
             cursor: rgb(0x278ad1),
             highlight: rgba(0x278ad130),
+            error: rgb(0xdc322f),
             border: rgba(0x2b4e58ff),
             separator: rgba(0x000000ff),
             scrollbar: rgba(0x0e6478ff),
@@ -328,13 +533,17 @@ impl Theme {
         }
     }
 
+    fn default_error() -> Rgba {
+        rgb(0xe5484d)
+    }
+
     fn custom() -> Self {
         let mut theme = Theme::dark();
         theme.variant = ThemeVariant::Custom;
         theme
     }
 
-    fn deserialize(cx: &mut App, config_content: String) -> ThemeVariant {
+    fn deserialize(cx: &mut App, config_content: String) -> Config {
         match toml::from_str(&config_content) {
             Ok(config) => config,
             Err(_) => {
@@ -343,18 +552,13 @@ impl Theme {
                     ToastVariant::Error,
                     "Failed to deserialize config. Defaulting to Dark theme",
                 );
-                Config {
-                    theme: ThemeVariant::Dark,
-                }
+                Config::new(ThemeVariant::Dark)
             }
         }
-        .theme
     }
 
-    fn serialize(cx: &mut App, theme_str: &str) -> String {
-        let theme: ThemeVariant = ThemeVariant::from_str(theme_str).unwrap_or(ThemeVariant::Dark);
-        let config = Config { theme };
-        match toml::to_string(&config) {
+    fn serialize(cx: &mut App, config: &Config) -> String {
+        match toml::to_string(config) {
             Ok(config_content) => config_content,
             Err(_) => {
                 toast(
@@ -390,9 +594,12 @@ impl Theme {
         Ok(config_content)
     }
 
-    fn write(cx: &mut App, theme_str: &str) {
-        let config_content = Theme::serialize(cx, theme_str);
+    /// Save theme_str as config.toml's theme, keeping the rest of the config
+    pub fn write(cx: &mut App, theme_str: &str) {
         let path = dirs::config_dir().unwrap_or_default().join("alc-calc");
+        let mut config = Theme::current_config(&path);
+        config.theme = ThemeVariant::from_str(theme_str).unwrap_or(ThemeVariant::Dark);
+        let config_content = Theme::serialize(cx, &config);
         if std::fs::metadata(&path).is_err() {
             match std::fs::create_dir(&path) {
                 Ok(_) => (),
@@ -400,23 +607,99 @@ impl Theme {
             }
         }
         match write(path.join("config.toml"), &config_content) {
-            Ok(_) => (),
+            Ok(_) => {
+                cx.default_global::<Watched>().written =
+                    std::fs::metadata(path.join("config.toml"))
+                        .and_then(|metadata| metadata.modified())
+                        .ok();
+            }
             Err(_) => toast(cx, ToastVariant::Error, "Failed to write to config file"),
         }
     }
 
-    fn deserialize_theme(cx: &mut App, theme_content: &str) -> Result<Theme, anyhow::Error> {
-        match toml::from_str(theme_content) {
-            Ok(theme) => Ok(theme),
-            Err(_) => {
-                toast(
-                    cx,
-                    ToastVariant::Error,
-                    "Failed to deserialize theme. Defaulting to Dark theme",
-                );
-                Ok(Theme::dark())
+    fn deserialize_theme(file: &str, theme_content: &str, errors: &mut Vec<String>) -> Theme {
+        match Theme::parse_theme(file, theme_content) {
+            Ok((theme, theme_errors)) => {
+                errors.extend(theme_errors);
+                theme
+            }
+            Err(error) => {
+                errors.push(format!("{error}. Defaulting to Dark theme"));
+                Theme::dark()
+            }
+        }
+    }
+
+    /// Build a custom theme from theme_content, which only needs to set the fields it
+    /// changes from its base (the default custom theme, or the built-in theme named by
+    /// `base`)
+    ///
+    /// Fails if theme_content isn't valid toml; otherwise each key that's unknown or whose
+    /// value isn't valid is described and left as its base value
+    fn parse_theme(file: &str, theme_content: &str) -> Result<(Theme, Vec<String>), String> {
+        let entries: BTreeMap<Spanned<String>, Spanned<toml::Value>> =
+            Theme::parse(file, theme_content)?;
+        let mut errors = vec![];
+        let at = |span: Range<usize>| {
+            let (line, column) = Theme::location(theme_content, span.start);
+            format!("in {file} at line {line}, column {column}")
+        };
+
+        let mut base = Theme::custom();
+        if let Some((key, value)) = entries.get_key_value("base") {
+            match value
+                .as_ref()
+                .as_str()
+                .and_then(|name| ThemeVariant::from_str(name).ok())
+                .and_then(|variant| Theme::builtin(&variant))
+            {
+                Some(theme) => base = theme,
+                None => errors.push((
+                    key.span().start,
+                    format!(
+                        "Invalid base {} {}: expected a built-in theme",
+                        value.as_ref(),
+                        at(key.span())
+                    ),
+                )),
+            }
+        }
+        let Ok(toml::Value::Table(mut table)) = toml::Value::try_from(&base) else {
+            return Err(String::from("Failed to serialize base theme"));
+        };
+
+        for (key, value) in entries.iter().filter(|(key, _)| key.as_ref() != "base") {
+            if !table.contains_key(key.as_ref()) {
+                errors.push((
+                    key.span().start,
+                    format!("Unknown key \"{}\" {}", key.as_ref(), at(key.span())),
+                ));
+                continue;
+            }
+            let mut candidate = table.clone();
+            candidate.insert(key.as_ref().clone(), value.as_ref().clone());
+            match toml::Value::Table(candidate.clone()).try_into::<Theme>() {
+                Ok(_) => table = candidate,
+                Err(error) => errors.push((
+                    value.span().start,
+                    format!(
+                        "Invalid value {} for {} {}: {}",
+                        value.as_ref(),
+                        key.as_ref(),
+                        at(value.span()),
+                        error.message().trim()
+                    ),
+                )),
             }
         }
+
+        let mut theme: Theme = toml::Value::Table(table)
+            .try_into()
+            .map_err(|error: toml::de::Error| error.message().to_string())?;
+        theme.variant = ThemeVariant::Custom;
+        // in the order they appear in the file, rather than the keys' alphabetical order
+        errors.sort_by_key(|(start, _)| *start);
+        Ok((theme, errors.into_iter().map(|(_, error)| error).collect()))
     }
 
     fn serialize_theme(cx: &mut App, theme: Theme) -> String {
@@ -433,29 +716,37 @@ impl Theme {
         }
     }
 
-    fn read_theme(cx: &mut App, path: PathBuf) -> Result<Theme, anyhow::Error> {
+    fn read_theme(path: PathBuf, errors: &mut Vec<String>) -> Result<Theme, anyhow::Error> {
         let file_path = path.join("theme.toml");
 
-        // prevents fs access on tests (namely, when Custom is selected in Theme::preview())
-        #[cfg(not(test))]
-        if std::fs::metadata(&file_path).is_err() {
-            Theme::write_theme(cx, path);
-        }
-
         let mut theme_file = File::open(file_path)?;
         let mut theme_content = String::new();
         match theme_file.read_to_string(&mut theme_content) {
             Ok(_) => (),
             Err(_) => {
                 theme_content = String::from(DEFAULT_CUSTOM_THEME);
-                toast(
-                    cx,
-                    ToastVariant::Error,
+                errors.push(String::from(
                     "Failed to read theme file. Defaulting to default custom theme",
-                );
+                ));
+            }
+        }
+        Ok(Theme::deserialize_theme(
+            "theme.toml",
+            &theme_content,
+            errors,
+        ))
+    }
+
+    fn read_named(path: &Path, file: &str, errors: &mut Vec<String>) -> Theme {
+        match std::fs::read_to_string(path.join(file)) {
+            Ok(theme_content) => Theme::deserialize_theme(file, &theme_content, errors),
+            Err(_) => {
+                errors.push(format!(
+                    "Failed to read {file}. Defaulting to default custom theme"
+                ));
+                Theme::custom()
             }
         }
-        Theme::deserialize_theme(cx, &theme_content)
     }
 
     // RA thinks this is dead code even though it is used
@@ -470,11 +761,142 @@ impl Theme {
         }
     }
 
-    // RA thinks this is dead code even though it is used
-    #[allow(dead_code)]
-    pub fn update(theme_str: &str, cx: &mut App) {
-        Theme::write(cx, theme_str);
-        Theme::set(cx);
+    /// Poll the config dir for changes to config.toml, theme.toml and the themes dir,
+    /// reapplying the theme whenever any of them is modified
+    ///
+    /// *Note that the dir is checked once a second (plenty for hand edits) on the
+    /// background executor, only returning to the main thread to reload*
+    pub fn watch(cx: &mut App) {
+        let path = dirs::config_dir().unwrap_or_default().join("alc-calc");
+        let executor = cx.background_executor().clone();
+
+        cx.spawn(async move |cx| {
+            let modified = |path: PathBuf| executor.spawn(async move { Theme::modified(&path) });
+            let mut last = modified(path.clone()).await;
+            loop {
+                executor.timer(Duration::from_secs(1)).await;
+                let current = modified(path.clone()).await;
+                if current == last {
+                    continue;
+                }
+                let result = cx.update(|cx| {
+                    if Theme::edited(cx, &last, &current) {
+                        Theme::reload(cx, &path);
+                    }
+                });
+                last = current;
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    // whether the config dir changed from last to current (each as from modified) by
+    // anything but the app's own write to config.toml
+    fn edited(cx: &App, last: &[Option<SystemTime>], current: &[Option<SystemTime>]) -> bool {
+        let written = cx
+            .try_global::<Watched>()
+            .and_then(|watched| watched.written);
+        current[1..] != last[1..] || (current[0] != last[0] && current[0] != written)
+    }
+
+    // the modification times of config.toml, theme.toml and each named theme's file
+    fn modified(path: &Path) -> Vec<Option<SystemTime>> {
+        let named = Theme::names(path)
+            .into_iter()
+            .filter_map(|name| Theme::file(&ThemeVariant::Named(name)));
+        [String::from("config.toml"), String::from("theme.toml")]
+            .into_iter()
+            .chain(named)
+            .map(|file| {
+                std::fs::metadata(path.join(file))
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Reread config.toml (and the selected theme's file, if it isn't built in) and apply
+    /// the result to every window, keeping the current theme if either fails to parse
+    fn reload(cx: &mut App, path: &Path) {
+        let Ok(config_content) = std::fs::read_to_string(path.join("config.toml")) else {
+            return;
+        };
+        let (config, mut errors) = match Theme::parse::<Config>("config.toml", &config_content) {
+            Ok(config) => Theme::known(config, path),
+            Err(error) => return toast(cx, ToastVariant::Error, &error),
+        };
+        let theme = match Theme::file(&Theme::follow(cx, &config.theme, &config)) {
+            Some(file) => {
+                let Ok(theme_content) = std::fs::read_to_string(path.join(&file)) else {
+                    return toast(
+                        cx,
+                        ToastVariant::Error,
+                        &format!("Failed to read {file}. Keeping the current theme"),
+                    );
+                };
+                match Theme::parse_theme(&file, &theme_content) {
+                    Ok((mut theme, theme_errors)) => {
+                        errors.extend(theme_errors);
+                        theme.variant = config.theme.clone();
+                        theme
+                    }
+                    Err(error) => return toast(cx, ToastVariant::Error, &error),
+                }
+            }
+            None => Theme::build(cx, &config.theme, &config, path.to_path_buf(), &mut errors),
+        };
+        let changed = cx.theme() != &theme;
+        if Theme::unreported(cx, &errors, changed) {
+            errors
+                .iter()
+                .for_each(|error| toast(cx, ToastVariant::Error, error));
+        }
+
+        if !changed {
+            return;
+        }
+        cx.set_global(theme);
+        if cx.has_global::<GlobalAppState>() {
+            AppState::global(cx).update(cx, |state, cx| {
+                state.settings.theme = config.theme;
+                cx.notify();
+            });
+        }
+        cx.refresh_windows();
+    }
+
+    // whether errors are worth reporting, which they aren't if they're the ones last
+    // reported and the theme didn't change
+    fn unreported(cx: &mut App, errors: &[String], changed: bool) -> bool {
+        let watched = cx.default_global::<Watched>();
+        let unreported = changed || watched.errors != errors;
+        watched.errors = errors.to_vec();
+        unreported
+    }
+
+    /// Deserialize content, describing where it went wrong if it couldn't be
+    fn parse<T: DeserializeOwned>(file: &str, content: &str) -> Result<T, String> {
+        toml::from_str(content).map_err(|error| {
+            let Some(span) = error.span() else {
+                return format!("Failed to parse {file}: {}", error.message());
+            };
+            let (line, column) = Theme::location(content, span.start);
+            format!(
+                "Failed to parse {file} at line {line}, column {column}: {}",
+                error.message()
+            )
+        })
+    }
+
+    // the 1-based line and column of offset in content
+    fn location(content: &str, offset: usize) -> (usize, usize) {
+        let before = &content[..offset.min(content.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+        (line, column)
     }
 
     // RA thinks this is dead code even though it is used in tests
@@ -492,50 +914,50 @@ mod tests {
     #[gpui::test]
     fn test_deserialize(cx: &mut TestAppContext) {
         let cx = cx.add_empty_window();
-        let mut theme = ThemeVariant::Dark;
-        let config_content = String::from("theme = \"SolarizedDark\"\n");
+        let mut config = Config::new(ThemeVariant::Dark);
+        let config_content = String::from("theme = \"Auto\"\nlight_theme = \"SolarizedDark\"\n");
 
         cx.update(|_, cx| {
-            theme = Theme::deserialize(cx, config_content);
+            config = Theme::deserialize(cx, config_content);
         });
 
-        assert!(theme == ThemeVariant::SolarizedDark);
+        assert_eq!(config.theme, ThemeVariant::Auto);
+        assert_eq!(config.light_theme, Some(ThemeVariant::SolarizedDark));
+        assert_eq!(config.dark_theme, None);
     }
 
     #[gpui::test]
     fn test_serialize(cx: &mut TestAppContext) {
         let cx = cx.add_empty_window();
-        let theme_str = "RosePineMoon";
+        let config = Config::new(ThemeVariant::RosePineMoon);
         let mut config_content = String::new();
         let expected = String::from("theme = \"RosePineMoon\"\n");
 
         cx.update(|_, cx| {
-            config_content = Theme::serialize(cx, theme_str);
+            config_content = Theme::serialize(cx, &config);
         });
 
         assert_eq!(config_content, expected);
     }
 
-    #[gpui::test]
-    fn test_deserialize_theme(cx: &mut TestAppContext) {
-        let cx = cx.add_empty_window();
-        let mut theme = Theme::dark();
+    #[test]
+    fn test_deserialize_theme() {
+        let mut errors = vec![];
         let expected = Theme::custom();
 
-        cx.update(|_, cx| {
-            theme = Theme::deserialize_theme(cx, DEFAULT_CUSTOM_THEME).unwrap();
-
-            // round these particular values due to lossy conversions from hsla -> rgb -> hsla
-            theme.text.l = crate::calc::round_to_place(theme.text.l, 1.0).unwrap();
-            theme.text.a = crate::calc::round_to_place(theme.text.a, 1.0).unwrap();
-            theme.subtext.l = crate::calc::round_to_place(theme.subtext.l, 1.0).unwrap();
-            theme.subtext.a = crate::calc::round_to_place(theme.subtext.a, 1.0).unwrap();
-            theme.inactivetext.l = crate::calc::round_to_place(theme.inactivetext.l, 1.0).unwrap();
-            theme.inactivetext.a = crate::calc::round_to_place(theme.inactivetext.a, 1.0).unwrap();
-            theme.field_text.l = crate::calc::round_to_place(theme.text.l, 1.0).unwrap();
-            theme.field_text.a = crate::calc::round_to_place(theme.text.a, 1.0).unwrap();
-        });
+        let mut theme = Theme::deserialize_theme("theme.toml", DEFAULT_CUSTOM_THEME, &mut errors);
+
+        // round these particular values due to lossy conversions from hsla -> rgb -> hsla
+        theme.text.l = crate::calc::round_to_place(theme.text.l, 1.0).unwrap();
+        theme.text.a = crate::calc::round_to_place(theme.text.a, 1.0).unwrap();
+        theme.subtext.l = crate::calc::round_to_place(theme.subtext.l, 1.0).unwrap();
+        theme.subtext.a = crate::calc::round_to_place(theme.subtext.a, 1.0).unwrap();
+        theme.inactivetext.l = crate::calc::round_to_place(theme.inactivetext.l, 1.0).unwrap();
+        theme.inactivetext.a = crate::calc::round_to_place(theme.inactivetext.a, 1.0).unwrap();
+        theme.field_text.l = crate::calc::round_to_place(theme.text.l, 1.0).unwrap();
+        theme.field_text.a = crate::calc::round_to_place(theme.text.a, 1.0).unwrap();
 
+        assert!(errors.is_empty());
         assert_eq!(theme, expected);
     }
 
@@ -551,4 +973,153 @@ mod tests {
 
         assert_eq!(theme_content, DEFAULT_CUSTOM_THEME);
     }
+
+    #[test]
+    fn test_parse_partial_theme() {
+        let theme_content = "base = \"Light\"\ncursor = \"#ff0000ff\"\n";
+        // hsla colors are written as hex, so the base is compared as it round-trips
+        let mut expected: Theme = toml::Value::try_from(Theme::light())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        expected.variant = ThemeVariant::Custom;
+        expected.cursor = rgba(0xff0000ff);
+
+        let (theme, errors) = Theme::parse_theme("theme.toml", theme_content).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(expected, theme);
+    }
+
+    #[test]
+    fn test_parse_theme_errors() {
+        let theme_content = "base = \"Auto\"\ncursr = \"#ff0000ff\"\nborder = \"#zz\"\n";
+
+        let (theme, errors) = Theme::parse_theme("theme.toml", theme_content).unwrap();
+
+        assert_eq!(Theme::custom().border, theme.border);
+        assert_eq!(3, errors.len());
+        assert_eq!(
+            "Invalid base \"Auto\" in theme.toml at line 1, column 1: expected a built-in theme",
+            errors[0]
+        );
+        assert_eq!(
+            "Unknown key \"cursr\" in theme.toml at line 2, column 1",
+            errors[1]
+        );
+        assert_eq!(
+            "Invalid value \"#zz\" for border in theme.toml at line 3, column 10: invalid RGBA \
+             hex color: '#zz'. Expected #rgb, #rgba, #rrggbb, or #rrggbbaa",
+            errors[2]
+        );
+        assert!(Theme::parse_theme("theme.toml", "cursor = ").is_err());
+    }
+
+    #[test]
+    fn test_named_variant() {
+        let config_content = "theme = \"Nord\"\n";
+        let config: Config = toml::from_str(config_content).unwrap();
+        let named = ThemeVariant::Named(String::from("Nord"));
+
+        assert_eq!(named, config.theme);
+        assert_eq!(config_content, toml::to_string(&config).unwrap());
+        assert_eq!(Ok(named.clone()), ThemeVariant::from_str("Nord"));
+        assert_eq!(Ok(ThemeVariant::RedDark), ThemeVariant::from_str("RedDark"));
+        assert_eq!("Nord", named.to_string());
+        assert_eq!(Some(String::from("themes/Nord.toml")), Theme::file(&named));
+    }
+
+    #[test]
+    fn test_known_themes() {
+        let mut config = Config::new(ThemeVariant::Named(String::from("Drak")));
+        config.light_theme = Some(ThemeVariant::Named(String::from("Nord")));
+        config.dark_theme = Some(ThemeVariant::Named(String::from("Nrod")));
+
+        let (config, errors) = Theme::known_in(config, &[String::from("Nord")]);
+
+        assert_eq!(ThemeVariant::Dark, config.theme);
+        assert_eq!(
+            Some(ThemeVariant::Named(String::from("Nord"))),
+            config.light_theme
+        );
+        assert_eq!(None, config.dark_theme);
+        assert_eq!(
+            vec![
+                "Unknown theme \"Drak\" in config.toml: expected a built-in theme or one in the \
+                 themes dir. Defaulting to Dark theme",
+                "Unknown theme \"Nrod\" for dark_theme in config.toml: expected a built-in theme \
+                 or one in the themes dir",
+            ],
+            errors
+        );
+    }
+
+    #[gpui::test]
+    fn test_follow(cx: &mut TestAppContext) {
+        let mut config = Config::new(ThemeVariant::Auto);
+        config.dark_theme = Some(ThemeVariant::RosePineMoon);
+        let mut followed = vec![];
+
+        // the test platform's appearance is always light
+        cx.update(|cx| {
+            followed.push(Theme::follow(cx, &config.theme, &config));
+            config.light_theme = Some(ThemeVariant::SolarizedDark);
+            followed.push(Theme::follow(cx, &config.theme, &config));
+            followed.push(Theme::follow(cx, &ThemeVariant::RedDark, &config));
+        });
+
+        assert_eq!(
+            vec![
+                ThemeVariant::Light,
+                ThemeVariant::SolarizedDark,
+                ThemeVariant::RedDark
+            ],
+            followed
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let config_content = "theme = \"Dark\"\ntheme = \"Light\"\n";
+
+        let result = Theme::parse::<Config>("config.toml", config_content);
+
+        assert_eq!(
+            Err("Failed to parse config.toml at line 2, column 1: duplicate key".into()),
+            result.map(|config| config.theme)
+        );
+    }
+
+    #[gpui::test]
+    fn test_edited(cx: &mut TestAppContext) {
+        let time = |secs| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let last = [time(1), time(1)];
+        let mut edited = vec![];
+
+        cx.update(|cx| {
+            cx.default_global::<Watched>().written = time(2);
+            edited.push(Theme::edited(cx, &last, &[time(2), time(1)]));
+            edited.push(Theme::edited(cx, &last, &[time(3), time(1)]));
+            edited.push(Theme::edited(cx, &last, &[time(2), time(2)]));
+        });
+
+        // the app's own write to config.toml isn't an edit, unlike any others
+        assert_eq!(vec![false, true, true], edited);
+    }
+
+    #[gpui::test]
+    fn test_unreported(cx: &mut TestAppContext) {
+        let errors = vec![String::from("Failed to parse theme.toml")];
+        let mut unreported = vec![];
+
+        cx.update(|cx| {
+            unreported.push(Theme::unreported(cx, &errors, false));
+            unreported.push(Theme::unreported(cx, &errors, false));
+            unreported.push(Theme::unreported(cx, &errors, true));
+            unreported.push(Theme::unreported(cx, &[], false));
+            unreported.push(Theme::unreported(cx, &errors, false));
+        });
+
+        assert_eq!(vec![true, false, true, true, true], unreported);
+    }
 }
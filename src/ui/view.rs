@@ -1,6 +1,8 @@
 // SPDX-FileCopyrightText: Camden Boren
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+pub mod help;
 pub mod menu;
+pub mod palette;
 pub mod table;
 pub mod titlebar;
@@ -0,0 +1,270 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ui::util::{keymap::Keymap, str::Spaceable, theme::ActiveTheme};
+use gpui::{
+    App, FocusHandle, Focusable, KeyBinding, KeyBindingContextPredicate, SharedString, Window,
+    actions, div, prelude::*, px,
+};
+
+actions!(help, [Escape]);
+
+const CONTEXT: &str = "Help";
+
+/// A context's bindings, in the order they were bound
+struct Group {
+    context: SharedString,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Entry {
+    action: SharedString,
+    keystrokes: Vec<SharedString>,
+    // the rest of the binding's predicate, if it's more than just the context
+    condition: Option<SharedString>,
+}
+
+/// An overlay listing every bound keybinding, grouped by the context it applies in
+pub struct HelpOverlay {
+    groups: Vec<Group>,
+    pub show: bool,
+    // where focus is returned once the overlay closes
+    target: Option<FocusHandle>,
+    focus_handle: FocusHandle,
+}
+
+impl HelpOverlay {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Keymap::bind(
+            cx,
+            CONTEXT,
+            [KeyBinding::new("escape", Escape, Some(CONTEXT))],
+        );
+
+        Self {
+            groups: vec![],
+            show: false,
+            target: None,
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// Open the overlay with the keymap as it's currently bound
+    pub fn show(
+        &mut self,
+        target: Option<FocusHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let keymap = cx.key_bindings();
+        self.groups = HelpOverlay::group(keymap.borrow().bindings());
+        self.target = target;
+        self.show = true;
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    /// Close the overlay, returning focus to where it was opened from
+    pub fn hide(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show = false;
+        if let Some(target) = self.target.take() {
+            target.focus(window);
+        }
+        cx.notify();
+    }
+
+    fn escape(&mut self, _: &Escape, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide(window, cx);
+    }
+
+    /// Group bindings by context (in the order each context was first bound), merging the
+    /// keystrokes of those that share an action and predicate
+    fn group<'a>(bindings: impl Iterator<Item = &'a KeyBinding>) -> Vec<Group> {
+        let mut groups: Vec<Group> = vec![];
+
+        for binding in bindings {
+            let predicate = binding.predicate();
+            let context: SharedString = predicate
+                .as_deref()
+                .and_then(HelpOverlay::context)
+                .unwrap_or("Global".into());
+            let condition: Option<SharedString> = predicate.as_deref().and_then(|predicate| {
+                let mut conjuncts = vec![];
+                HelpOverlay::conjuncts(predicate, &mut conjuncts);
+                let condition: Vec<String> = conjuncts
+                    .iter()
+                    .skip(1)
+                    .map(|conjunct| conjunct.to_string())
+                    .collect();
+                (!condition.is_empty()).then(|| condition.join(" && ").into())
+            });
+            let name = binding.action().name();
+            let action = SharedString::from(name.rsplit("::").next().unwrap_or(name).to_string())
+                .insert_spaces();
+            let keystrokes = Keymap::keystrokes(binding);
+
+            let ix = match groups.iter().position(|group| group.context == context) {
+                Some(ix) => ix,
+                None => {
+                    groups.push(Group {
+                        context,
+                        entries: vec![],
+                    });
+                    groups.len() - 1
+                }
+            };
+            let entries = &mut groups[ix].entries;
+            match entries
+                .iter_mut()
+                .find(|entry| entry.action == action && entry.condition == condition)
+            {
+                Some(entry) => entry.keystrokes.push(keystrokes),
+                None => entries.push(Entry {
+                    action,
+                    keystrokes: vec![keystrokes],
+                    condition,
+                }),
+            }
+        }
+
+        groups
+    }
+
+    /// The context a predicate is rooted in, e.g. Table for `Table && navigating`
+    fn context(predicate: &KeyBindingContextPredicate) -> Option<SharedString> {
+        match predicate {
+            KeyBindingContextPredicate::Identifier(context) => Some(context.clone()),
+            KeyBindingContextPredicate::And(left, _)
+            | KeyBindingContextPredicate::Descendant(left, _) => HelpOverlay::context(left),
+            _ => None,
+        }
+    }
+
+    // flatten a chain of &&s, e.g. `(Table && a) && b` into [Table, a, b]
+    fn conjuncts<'a>(
+        predicate: &'a KeyBindingContextPredicate,
+        conjuncts: &mut Vec<&'a KeyBindingContextPredicate>,
+    ) {
+        match predicate {
+            KeyBindingContextPredicate::And(left, right) => {
+                HelpOverlay::conjuncts(left, conjuncts);
+                HelpOverlay::conjuncts(right, conjuncts);
+            }
+            _ => conjuncts.push(predicate),
+        }
+    }
+}
+
+impl Render for HelpOverlay {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .when(self.show, |this| {
+                this.on_action(cx.listener(Self::escape)).child(
+                    div()
+                        .id("help_overlay")
+                        .occlude()
+                        .flex()
+                        .flex_col()
+                        .gap_3()
+                        // centered on the (empty) spot UI lays the overlay out in
+                        .absolute()
+                        .left(px(-240.))
+                        .w(px(480.))
+                        .h(px(420.))
+                        .mt_10()
+                        .p_3()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .bg(cx.theme().field)
+                        .text_sm()
+                        .text_color(cx.theme().field_text)
+                        .overflow_y_scroll()
+                        .children(self.groups.iter().map(|group| {
+                            div()
+                                .flex()
+                                .flex_col()
+                                .child(
+                                    div()
+                                        .pb_1()
+                                        .mb_1()
+                                        .border_b_1()
+                                        .border_color(cx.theme().background)
+                                        .text_color(cx.theme().subtext)
+                                        .child(group.context.insert_spaces()),
+                                )
+                                .children(group.entries.iter().map(|entry| {
+                                    div()
+                                        .flex()
+                                        .flex_row()
+                                        .justify_between()
+                                        .gap_2()
+                                        .child(
+                                            div()
+                                                .flex()
+                                                .flex_row()
+                                                .gap_1()
+                                                .child(entry.action.clone())
+                                                .children(entry.condition.clone().map(
+                                                    |condition| {
+                                                        div()
+                                                            .text_color(cx.theme().subtext)
+                                                            .child(format!("({condition})"))
+                                                    },
+                                                )),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_color(cx.theme().subtext)
+                                                .child(entry.keystrokes.join(", ")),
+                                        )
+                                }))
+                        }))
+                        .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                            cx.stop_propagation();
+                            this.hide(window, cx);
+                        })),
+                )
+            })
+    }
+}
+
+impl Focusable for HelpOverlay {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::{Tab, TabPrev, Toggle};
+
+    #[test]
+    fn test_help_group() {
+        let bindings = [
+            KeyBinding::new("tab", Tab, Some("UI")),
+            KeyBinding::new("escape", Escape, Some("Help")),
+            KeyBinding::new("shift-tab", TabPrev, Some("UI")),
+            KeyBinding::new("f2", Toggle, Some("UI && !TextInput && !Help")),
+            KeyBinding::new("f3", Toggle, Some("UI && !TextInput && !Help")),
+        ];
+
+        let groups = HelpOverlay::group(bindings.iter());
+        let contexts: Vec<&str> = groups.iter().map(|g| g.context.as_ref()).collect();
+
+        assert_eq!(vec!["UI", "Help"], contexts);
+        assert_eq!(3, groups[0].entries.len());
+        assert_eq!(
+            Entry {
+                action: "Toggle".into(),
+                keystrokes: vec!["f2".into(), "f3".into()],
+                condition: Some("!TextInput && !Help".into()),
+            },
+            groups[0].entries[2]
+        );
+    }
+}
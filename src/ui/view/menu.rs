@@ -2,23 +2,25 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use crate::ui::{
-    ActiveCtrl,
+    CONTEXT as UI_CONTEXT, Toggle,
     comp::{
         button::{icon_button, text_button},
         icon::{Icon, IconSize, IconVariant},
         tooltip::Tooltip,
     },
     util::{
+        keymap::Keymap,
+        state::AppState,
         str::Spaceable,
         theme::{ActiveTheme, Theme, ThemeVariant},
     },
 };
 use gpui::{
-    App, FocusHandle, Focusable, KeyBinding, SharedString, Window, actions, div, prelude::*, px,
-    uniform_list,
+    App, FocusHandle, Focusable, KeyBinding, SharedString, Subscription, Window, actions, div,
+    prelude::*, px, uniform_list,
 };
 use std::ops::Range;
-use strum::{EnumCount, IntoEnumIterator};
+use strum::IntoEnumIterator;
 
 actions!(theme_menu, [Escape, Enter, Next, Prev, Select]);
 
@@ -31,34 +33,59 @@ pub struct ThemeMenu {
     count: usize,
     focused_item: usize,
     focus_handle: FocusHandle,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl ThemeMenu {
     pub fn new(cx: &mut Context<Self>) -> Self {
-        cx.bind_keys([
-            KeyBinding::new("escape", Escape, Some(CONTEXT)),
-            KeyBinding::new("enter", Enter, Some(CONTEXT)),
-            KeyBinding::new("up", Prev, Some(CONTEXT)),
-            KeyBinding::new("k", Prev, Some(CONTEXT)),
-            KeyBinding::new("down", Next, Some(CONTEXT)),
-            KeyBinding::new("j", Next, Some(CONTEXT)),
-            KeyBinding::new("enter", Select, Some(CONTEXT)),
-        ]);
-
-        let variants: Vec<SharedString> = ThemeVariant::iter()
-            .map(|t| SharedString::from(t.to_string()))
-            .collect();
-        let current = cx.theme().variant.to_string().into();
-        let focused_item = ThemeMenu::index_of(&variants, &current);
+        Keymap::bind(
+            cx,
+            CONTEXT,
+            [
+                KeyBinding::new("escape", Escape, Some(CONTEXT)),
+                KeyBinding::new("enter", Enter, Some(CONTEXT)),
+                KeyBinding::new("up", Prev, Some(CONTEXT)),
+                KeyBinding::new("k", Prev, Some(CONTEXT)),
+                KeyBinding::new("down", Next, Some(CONTEXT)),
+                KeyBinding::new("j", Next, Some(CONTEXT)),
+                KeyBinding::new("enter", Select, Some(CONTEXT)),
+            ],
+        );
 
-        Self {
-            variants,
+        let mut menu = Self {
+            variants: vec![],
             prev: None,
             show: false,
-            count: ThemeVariant::COUNT,
-            focused_item,
+            count: 0,
+            focused_item: 0,
             focus_handle: cx.focus_handle(),
-        }
+            _subscriptions: vec![
+                // follow themes picked in other windows or by editing config.toml
+                cx.observe(&AppState::global(cx), |this: &mut ThemeMenu, state, cx| {
+                    if !this.show {
+                        let current = state.read(cx).settings.theme.to_string().into();
+                        this.focused_item = ThemeMenu::index_of(&this.variants, &current);
+                        cx.notify();
+                    }
+                }),
+            ],
+        };
+        menu.refresh(cx);
+        menu
+    }
+
+    // relist the built-in themes followed by those in the themes dir, which may have
+    // changed since the menu was last opened
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.variants = ThemeVariant::iter()
+            .filter(|variant| !matches!(variant, ThemeVariant::Named(_)))
+            .map(|variant| variant.to_string())
+            .chain(Theme::named())
+            .map(SharedString::from)
+            .collect();
+        self.count = self.variants.len();
+        let current = cx.theme().variant.to_string().into();
+        self.focused_item = ThemeMenu::index_of(&self.variants, &current);
     }
 
     pub fn focus(&self, window: &mut Window) {
@@ -72,9 +99,7 @@ impl ThemeMenu {
     fn update(&mut self, val: SharedString, cx: &mut Context<Self>, toggle: bool) {
         self.focused_item = ThemeMenu::index_of(&self.variants, &val);
 
-        // prevents fs access on tests
-        #[cfg(not(test))]
-        Theme::update(&val, cx);
+        AppState::global(cx).update(cx, |state, cx| state.set_theme(&val, cx));
 
         if toggle {
             self.toggle(cx);
@@ -85,6 +110,7 @@ impl ThemeMenu {
         if self.show {
             self.show = false;
         } else {
+            self.refresh(cx);
             self.prev = Some(cx.theme().variant.to_string().into());
             self.show = true;
         }
@@ -114,6 +140,7 @@ impl ThemeMenu {
     }
 
     fn show_key(&mut self, _: &Enter, _window: &mut Window, cx: &mut Context<Self>) {
+        self.refresh(cx);
         self.show = true;
         self.prev = Some(cx.theme().variant.to_string().into());
         cx.notify();
@@ -196,9 +223,13 @@ impl Render for ThemeMenu {
                         cx.listener(move |this, _, _, cx| this.toggle(cx)),
                     ))
                     .id("menu_button")
-                    .tooltip(|_window, cx| {
-                        cx.new(|cx| Tooltip::new("Theme Menu").keybind(&format!("{}-t", cx.ctrl())))
-                            .into()
+                    .tooltip(|window, cx| {
+                        let keybind = Keymap::keybind(window, &Toggle, UI_CONTEXT);
+                        cx.new(|_cx| {
+                            Tooltip::new("Theme Menu")
+                                .when_some(keybind, |this, keybind| this.keybind(&keybind))
+                        })
+                        .into()
                     }),
             )
             .when(self.show, |this| {
@@ -268,7 +299,7 @@ mod tests {
     use super::*;
     use gpui::{Entity, TestAppContext, VisualTestContext};
 
-    const MAX_INDEX: usize = 5;
+    const MAX_INDEX: usize = 6;
 
     #[gpui::test]
     fn test_menu_update(cx: &mut TestAppContext) {
@@ -283,6 +314,17 @@ mod tests {
         assert_eq!(ThemeVariant::Light, result);
     }
 
+    #[gpui::test]
+    fn test_menu_variants(cx: &mut TestAppContext) {
+        let (menu, cx) = setup_menu(cx);
+        let mut variants = vec![];
+
+        menu.update(cx, |menu, _cx| variants = menu.variants.clone());
+
+        assert_eq!(MAX_INDEX + 1, variants.len());
+        assert_eq!(Some(&SharedString::from("Auto")), variants.last());
+    }
+
     #[gpui::test]
     fn test_menu_select(cx: &mut TestAppContext) {
         let (menu, cx) = setup_menu(cx);
@@ -301,7 +343,7 @@ mod tests {
         });
 
         assert_eq!(1, result);
-        assert_eq!(false, show)
+        assert!(!show)
     }
 
     #[gpui::test]
@@ -336,8 +378,22 @@ mod tests {
         assert_eq!(MAX_INDEX, result)
     }
 
+    #[gpui::test]
+    fn test_menu_follows_state(cx: &mut TestAppContext) {
+        let (menu, cx) = setup_menu(cx);
+        let mut result = 0;
+
+        cx.update(|_window, cx| {
+            AppState::global(cx).update(cx, |state, cx| state.set_theme("Light", cx))
+        });
+        menu.update(cx, |menu, _cx| result = menu.focused_item);
+
+        assert_eq!(1, result);
+    }
+
     fn setup_menu(cx: &mut TestAppContext) -> (Entity<ThemeMenu>, &mut VisualTestContext) {
         Theme::test(cx);
+        AppState::test(cx);
         cx.add_window_view(|_window, cx| ThemeMenu::new(cx))
     }
 }
@@ -0,0 +1,416 @@
+// SPDX-FileCopyrightText: Camden Boren
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ui::util::{app_menu::label, fuzzy::fuzzy_filter, keymap::Keymap, theme::ActiveTheme};
+use gpui::{
+    Action, App, FocusHandle, Focusable, KeyBinding, KeyDownEvent, ScrollStrategy, SharedString,
+    UniformListScrollHandle, Window, actions, div, prelude::*, px, uniform_list,
+};
+use std::ops::Range;
+
+actions!(command_palette, [Escape, Next, Prev, Select]);
+
+const CONTEXT: &str = "CommandPalette";
+// actions that only make sense within a component or from a keybind, so aren't listed,
+// either by name or by namespace (ending in ::)
+const EXCLUDED: &[&str] = &[
+    "command_palette::",
+    "dropdown::",
+    "help::",
+    "text_input::",
+    "theme_menu::",
+    "zed::",
+    "table::CellDown",
+    "table::CellLeft",
+    "table::CellRight",
+    "table::CellUp",
+    "table::EditCell",
+    "table::Escape",
+    "ui::OpenPalette",
+    "ui::Tab",
+    "ui::TabPrev",
+    // only handled on macOS, from the app menu
+    #[cfg(not(target_os = "macos"))]
+    "ui::About",
+    #[cfg(not(target_os = "macos"))]
+    "ui::Hide",
+    #[cfg(not(target_os = "macos"))]
+    "ui::Minimize",
+];
+
+struct Command {
+    name: SharedString,
+    action: Box<dyn Action>,
+    keybind: Option<SharedString>,
+}
+
+/// An overlay for running any of the app's commands by name
+pub struct CommandPalette {
+    commands: Vec<Command>,
+    // indices into commands, best match first
+    matches: Vec<usize>,
+    query: String,
+    pub show: bool,
+    focused_item: usize,
+    // where commands are dispatched (and focus is returned) once the palette closes
+    target: Option<FocusHandle>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+}
+
+impl CommandPalette {
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Keymap::bind(
+            cx,
+            CONTEXT,
+            [
+                KeyBinding::new("escape", Escape, Some(CONTEXT)),
+                KeyBinding::new("up", Prev, Some(CONTEXT)),
+                KeyBinding::new("down", Next, Some(CONTEXT)),
+                KeyBinding::new("enter", Select, Some(CONTEXT)),
+            ],
+        );
+
+        let commands = CommandPalette::commands(cx);
+
+        Self {
+            matches: (0..commands.len()).collect(),
+            commands,
+            query: String::new(),
+            show: false,
+            focused_item: 0,
+            target: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+        }
+    }
+
+    /// Every registered action that isn't excluded, named as in the app menu (e.g.,
+    /// table::CopyCsv is "Copy as CSV")
+    fn commands(cx: &App) -> Vec<Command> {
+        cx.all_action_names()
+            .iter()
+            .filter(|name| !CommandPalette::excluded(name))
+            .filter_map(|name| {
+                Some(Command {
+                    name: label(name)?.into(),
+                    action: cx.build_action(name, None).ok()?,
+                    keybind: None,
+                })
+            })
+            .collect()
+    }
+
+    fn excluded(name: &str) -> bool {
+        EXCLUDED
+            .iter()
+            .any(|excluded| match excluded.ends_with("::") {
+                true => name.starts_with(excluded),
+                false => name == *excluded,
+            })
+    }
+
+    /// Open the palette for commands to run on target, listing the keybinds they'd have
+    /// there
+    pub fn show(
+        &mut self,
+        target: Option<FocusHandle>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.commands.iter_mut().for_each(|command| {
+            let binding = match &target {
+                Some(target) => {
+                    window.highest_precedence_binding_for_action_in(command.action.as_ref(), target)
+                }
+                None => window.highest_precedence_binding_for_action(command.action.as_ref()),
+            };
+            command.keybind = binding.map(|binding| Keymap::keystrokes(&binding));
+        });
+
+        self.target = target;
+        self.show = true;
+        self.filter(String::new());
+        self.focus_handle.focus(window);
+        cx.notify();
+    }
+
+    fn filter(&mut self, query: String) {
+        let names: Vec<&str> = self.commands.iter().map(|c| c.name.as_ref()).collect();
+        self.matches = fuzzy_filter(&query, &names);
+        self.query = query;
+        self.focused_item = 0;
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+    }
+
+    /// Close the palette, returning focus to where it was opened from
+    fn hide(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show = false;
+        if let Some(target) = &self.target {
+            target.focus(window);
+        }
+        cx.notify();
+    }
+
+    /// Close the palette and run the ix-th match's command
+    fn run(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(command) = self.matches.get(ix).and_then(|ix| self.commands.get(*ix)) else {
+            return;
+        };
+        let action = command.action.boxed_clone();
+        self.hide(window, cx);
+        window.dispatch_action(action, cx);
+    }
+
+    fn escape(&mut self, _: &Escape, window: &mut Window, cx: &mut Context<Self>) {
+        self.hide(window, cx);
+    }
+
+    fn select(&mut self, _: &Select, window: &mut Window, cx: &mut Context<Self>) {
+        self.run(self.focused_item, window, cx);
+    }
+
+    fn next(&mut self, _: &Next, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step(true, cx);
+    }
+
+    fn prev(&mut self, _: &Prev, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step(false, cx);
+    }
+
+    fn step(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let count = self.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.focused_item = match forward {
+            true => (self.focused_item + 1) % count,
+            false => self.focused_item.checked_sub(1).unwrap_or(count - 1),
+        };
+        self.scroll_handle
+            .scroll_to_item(self.focused_item, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        if keystroke.modifiers.control || keystroke.modifiers.platform {
+            return;
+        }
+
+        let mut query = self.query.clone();
+        match (keystroke.key.as_str(), &keystroke.key_char) {
+            ("backspace", _) => {
+                query.pop();
+            }
+            (_, Some(key_char)) if !key_char.chars().any(char::is_control) => {
+                query.push_str(key_char);
+            }
+            _ => return,
+        }
+
+        cx.stop_propagation();
+        self.filter(query);
+        cx.notify();
+    }
+}
+
+impl Render for CommandPalette {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .when(self.show, |this| {
+                this.on_action(cx.listener(Self::escape))
+                    .on_action(cx.listener(Self::select))
+                    .on_action(cx.listener(Self::next))
+                    .on_action(cx.listener(Self::prev))
+                    .on_key_down(cx.listener(Self::on_key_down))
+                    .child(
+                        div()
+                            .id("command_palette")
+                            .occlude()
+                            .flex()
+                            .flex_col()
+                            // centered on the (empty) spot UI lays the palette out in
+                            .absolute()
+                            .left(px(-180.))
+                            .w(px(360.))
+                            .h(px(280.))
+                            .mt_10()
+                            .p_1()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .bg(cx.theme().field)
+                            .text_base()
+                            .text_color(cx.theme().field_text)
+                            .child(
+                                div()
+                                    .px_1()
+                                    .pb_1()
+                                    .mb_1()
+                                    .border_b_1()
+                                    .border_color(cx.theme().background)
+                                    .when(self.query.is_empty(), |this| {
+                                        this.text_color(cx.theme().subtext)
+                                            .child("Type a command...")
+                                    })
+                                    .when(!self.query.is_empty(), |this| {
+                                        this.child(self.query.clone())
+                                    })
+                                    .when(self.matches.is_empty(), |this| {
+                                        this.child(
+                                            div()
+                                                .text_color(cx.theme().subtext)
+                                                .child("No matches"),
+                                        )
+                                    }),
+                            )
+                            .child(
+                                uniform_list(
+                                    "commands_list",
+                                    self.matches.len(),
+                                    cx.processor(|this, range: Range<usize>, _window, cx| {
+                                        range
+                                            .filter_map(|ix| {
+                                                let command =
+                                                    this.commands.get(*this.matches.get(ix)?)?;
+                                                Some(
+                                                    div()
+                                                        .id(("command", ix))
+                                                        .flex()
+                                                        .flex_row()
+                                                        .justify_between()
+                                                        .rounded_md()
+                                                        .px_1()
+                                                        .cursor_pointer()
+                                                        .hover(|this| {
+                                                            this.bg(cx.theme().background)
+                                                        })
+                                                        .when(this.focused_item == ix, |this| {
+                                                            this.bg(cx.theme().background)
+                                                        })
+                                                        .child(command.name.clone())
+                                                        .children(command.keybind.clone().map(
+                                                            |keybind| {
+                                                                div()
+                                                                    .text_color(cx.theme().subtext)
+                                                                    .child(keybind)
+                                                            },
+                                                        ))
+                                                        .on_click(cx.listener(
+                                                            move |this, _, window, cx| {
+                                                                this.run(ix, window, cx)
+                                                            },
+                                                        )),
+                                                )
+                                            })
+                                            .collect()
+                                    }),
+                                )
+                                .track_scroll(self.scroll_handle.clone())
+                                .flex_1()
+                                .min_h_0(),
+                            )
+                            .on_mouse_down_out(cx.listener(|this, _, window, cx| {
+                                cx.stop_propagation();
+                                this.hide(window, cx);
+                            })),
+                    )
+            })
+    }
+}
+
+impl Focusable for CommandPalette {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::util::theme::Theme;
+    use gpui::{Entity, TestAppContext, VisualTestContext};
+
+    #[gpui::test]
+    fn test_palette_filter(cx: &mut TestAppContext) {
+        let (palette, cx) = setup_palette(cx);
+        let mut names: Vec<SharedString> = vec![];
+
+        cx.simulate_keystrokes("n e w space t");
+        palette.update(cx, |palette, _cx| {
+            names = palette
+                .matches
+                .iter()
+                .map(|ix| palette.commands[*ix].name.clone())
+                .collect();
+        });
+
+        assert_eq!(vec![SharedString::from("New Tab")], names);
+    }
+
+    #[gpui::test]
+    fn test_palette_prev_at_limit(cx: &mut TestAppContext) {
+        let (palette, cx) = setup_palette(cx);
+        let mut result = (0, 0);
+
+        cx.simulate_keystrokes("up");
+        palette.update(cx, |palette, _cx| {
+            result = (palette.commands.len() - 1, palette.focused_item)
+        });
+
+        assert_eq!(result.0, result.1);
+    }
+
+    #[gpui::test]
+    fn test_palette_commands(cx: &mut TestAppContext) {
+        let (palette, cx) = setup_palette(cx);
+        let mut names: Vec<SharedString> = vec![];
+
+        palette.update(cx, |palette, _cx| {
+            names = palette.commands.iter().map(|c| c.name.clone()).collect();
+        });
+
+        assert!(names.contains(&"Copy as CSV".into()));
+        assert!(names.contains(&"Remove Ingredient".into()));
+        assert!(!names.contains(&"Tab".into()));
+        assert!(!names.contains(&"Backspace".into()));
+    }
+
+    #[gpui::test]
+    fn test_palette_labels(cx: &mut TestAppContext) {
+        let (_palette, cx) = setup_palette(cx);
+        let mut unlabeled: Vec<&str> = vec![];
+
+        cx.update(|_window, cx| {
+            unlabeled = cx
+                .all_action_names()
+                .iter()
+                .filter(|name| !CommandPalette::excluded(name) && label(name).is_none())
+                .copied()
+                .collect();
+        });
+
+        assert!(unlabeled.is_empty(), "{unlabeled:?} have no label");
+    }
+
+    #[gpui::test]
+    fn test_palette_escape(cx: &mut TestAppContext) {
+        let (palette, cx) = setup_palette(cx);
+        let mut show = true;
+
+        cx.simulate_keystrokes("u n escape");
+        palette.update(cx, |palette, _cx| show = palette.show);
+
+        assert!(!show);
+    }
+
+    fn setup_palette(cx: &mut TestAppContext) -> (Entity<CommandPalette>, &mut VisualTestContext) {
+        Theme::test(cx);
+        let (palette, cx) = cx.add_window_view(|_window, cx| CommandPalette::new(cx));
+        palette.update_in(cx, |palette, window, cx| palette.show(None, window, cx));
+        (palette, cx)
+    }
+}
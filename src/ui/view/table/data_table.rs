@@ -5,29 +5,73 @@
 
 use crate::{
     calc::calc_weights,
+    export::ExportFormat,
+    import::parse_recipe,
     ui::{
-        ActiveCtrl,
+        ActiveCtrl, Tab, TabPrev,
         comp::{
             button::icon_button,
-            dropdown::Dropdown,
+            dropdown::{
+                Dropdown, SCROLLBAR_THUMB_HEIGHT, SCROLLBAR_THUMB_WIDTH, TypeChanged, TypeSelected,
+            },
             icon::{Icon, IconSize, IconVariant},
-            input::text_input::TextInput,
+            input::text_input::{ContentChanged, Numeric, Paste, TextInput},
             toast::{ToastVariant, toast},
             tooltip::Tooltip,
         },
-        util::theme::ActiveTheme,
-        view::table::ingredient::{FIELDS, Ingredient, IngredientData},
+        util::{
+            focus::FocusOrder,
+            keymap::Keymap,
+            session::{Recipe, RecipeIngredient},
+            str::Spaceable,
+            theme::ActiveTheme,
+        },
+        view::table::ingredient::{
+            FIELDS, Ingredient, IngredientData, Move, ROW_HEIGHT, Remove, TAB_STOPS,
+        },
     },
 };
 use gpui::{
-    App, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding, SharedString, Window, actions,
-    div, prelude::*, px,
+    App, ClipboardItem, Entity, EventEmitter, FocusHandle, Focusable, KeyBinding, KeyContext,
+    ScrollStrategy, SharedString, UniformListScrollHandle, Window, actions, div, prelude::*, px,
+    uniform_list,
 };
-
-actions!(table, [Add, Delete, Escape, RemoveKey]);
+use std::ops::Range;
+
+actions!(
+    table,
+    [
+        Add,
+        Clear,
+        Delete,
+        Escape,
+        CellUp,
+        CellDown,
+        CellLeft,
+        CellRight,
+        EditCell,
+        RemoveKey,
+        InsertAbove,
+        InsertBelow,
+        Duplicate,
+        MoveUp,
+        MoveDown,
+        Undo,
+        Redo,
+        CopyCsv,
+        CopyMarkdown,
+        CopyText,
+        SaveCsv,
+        SaveMarkdown,
+        SaveText
+    ]
+);
 
 pub const CONTEXT: &str = "Table";
-pub const MAX_ITEMS: usize = 10;
+// set while a cell is selected but not being edited
+const NAVIGATING: &str = "navigating";
+const MAX_HISTORY: usize = 100;
+const MAX_VISIBLE_ROWS: usize = 8;
 
 pub struct Table {
     pub ingreds: Vec<Entity<Ingredient>>,
@@ -35,62 +79,240 @@ pub struct Table {
     num_drinks: f32,
     count: usize,
     init: bool,
+    recipe: Recipe,
+    undo_stack: Vec<Recipe>,
+    redo_stack: Vec<Recipe>,
     focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    // (row, column) of the cell selected while navigating
+    navigating: Option<(usize, usize)>,
+    // whether an input changed since weights were last calculated
+    dirty: bool,
+    // last calculated ingredients, or None if they weren't ready
+    calculated: Option<Vec<IngredientData>>,
 }
 
 impl Table {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let ctrl = cx.ctrl();
-        cx.bind_keys([
-            KeyBinding::new(&format!("{ctrl}-i"), Add, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-d"), Delete, Some(CONTEXT)),
-            KeyBinding::new(&format!("{ctrl}-r"), RemoveKey, Some(CONTEXT)),
-            KeyBinding::new("escape", Escape, Some(CONTEXT)),
-        ]);
+        let navigating = format!("{CONTEXT} && {NAVIGATING}");
+        Keymap::bind(
+            cx,
+            CONTEXT,
+            [
+                KeyBinding::new(&format!("{ctrl}-i"), Add, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-d"), Delete, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-r"), RemoveKey, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-r"), Clear, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-enter"), InsertAbove, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-enter"), InsertBelow, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-d"), Duplicate, Some(CONTEXT)),
+                KeyBinding::new("alt-up", MoveUp, Some(CONTEXT)),
+                KeyBinding::new("alt-down", MoveDown, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-z"), Undo, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-z"), Redo, Some(CONTEXT)),
+                KeyBinding::new("escape", Escape, Some(CONTEXT)),
+                KeyBinding::new("up", CellUp, Some(&navigating)),
+                KeyBinding::new("k", CellUp, Some(&navigating)),
+                KeyBinding::new("down", CellDown, Some(&navigating)),
+                KeyBinding::new("j", CellDown, Some(&navigating)),
+                KeyBinding::new("left", CellLeft, Some(&navigating)),
+                KeyBinding::new("h", CellLeft, Some(&navigating)),
+                KeyBinding::new("right", CellRight, Some(&navigating)),
+                KeyBinding::new("l", CellRight, Some(&navigating)),
+                KeyBinding::new("enter", EditCell, Some(&navigating)),
+                KeyBinding::new(&format!("{ctrl}-e"), CopyMarkdown, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-shift-e"), CopyCsv, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-s"), SaveCsv, Some(CONTEXT)),
+                KeyBinding::new(&format!("{ctrl}-v"), Paste, Some(CONTEXT)),
+            ],
+        );
+
+        let num_drinks_input = cx.new(|cx| {
+            TextInput::new(window, cx, "Type here...".into()).numeric(Numeric::default())
+        });
+        cx.subscribe(&num_drinks_input, |this, _, _: &ContentChanged, cx| {
+            this.mark_dirty(cx);
+            cx.emit(Changed {});
+        })
+        .detach();
+        cx.on_blur(
+            &num_drinks_input.focus_handle(cx),
+            window,
+            |this, _window, cx| this.record(cx),
+        )
+        .detach();
 
         Self {
             ingreds: vec![],
-            num_drinks_input: cx.new(|cx| TextInput::new(window, cx, "Type here...".into(), 1)),
+            num_drinks_input,
             num_drinks: 0.,
             count: 0,
             init: true,
+            recipe: Recipe::default(),
+            undo_stack: vec![],
+            redo_stack: vec![],
             focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            navigating: None,
+            dirty: true,
+            calculated: None,
         }
     }
 
     fn add(&mut self, _: &Add, window: &mut Window, cx: &mut Context<Self>) {
-        if self.count < MAX_ITEMS {
-            let id = self.count;
-            let ingred = cx.new(|cx| Ingredient::new(id, window, cx));
-
-            // subscribe to Ingred's Remove event
-            cx.subscribe(
-                &ingred,
-                |this: &mut Table, ingred: Entity<Ingredient>, _event, cx| {
-                    this.remove(ingred.read(cx).id, cx)
-                },
-            )
-            .detach();
+        self.record(cx);
+        self.push(window, cx);
+        self.record(cx);
+        self.scroll_handle
+            .scroll_to_item(self.count - 1, ScrollStrategy::Top);
+        cx.notify();
+    }
 
-            self.ingreds.push(ingred);
-            self.count += 1;
+    fn push(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Entity<Ingredient> {
+        self.insert(self.count, window, cx)
+    }
 
-            // instruct UI to subscribe new ingreds to its Tab, TabPrev events
-            cx.emit(Add {});
+    fn insert(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Ingredient> {
+        let ix = ix.min(self.count);
+        let ingred = cx.new(|cx| Ingredient::new(ix, window, cx));
+
+        // subscribe to Ingred's Remove, Move, and row menu events
+        cx.subscribe(
+            &ingred,
+            |this: &mut Table, ingred: Entity<Ingredient>, _: &Remove, cx| {
+                this.record(cx);
+                this.remove(ingred.read(cx).id, cx);
+                this.record(cx);
+            },
+        )
+        .detach();
+        cx.subscribe(&ingred, |this: &mut Table, _ingred, event: &Move, cx| {
+            this.move_ingred(event.from, event.to, cx)
+        })
+        .detach();
+        cx.subscribe_in(
+            &ingred,
+            window,
+            |this: &mut Table, ingred, _: &InsertAbove, window, cx| {
+                this.insert_row(ingred.read(cx).id, None, window, cx)
+            },
+        )
+        .detach();
+        cx.subscribe_in(
+            &ingred,
+            window,
+            |this: &mut Table, ingred, _: &InsertBelow, window, cx| {
+                this.insert_row(ingred.read(cx).id + 1, None, window, cx)
+            },
+        )
+        .detach();
+        cx.subscribe_in(
+            &ingred,
+            window,
+            |this: &mut Table, ingred, _: &Duplicate, window, cx| {
+                this.duplicate_row(ingred.read(cx).id, window, cx)
+            },
+        )
+        .detach();
+
+        // recalculate when any of its fields change (including previewed types), and
+        // record a selected type or a field's edits (once it loses focus) as an undo step
+        let ingred_type = ingred.read(cx).ingred_type.clone();
+        cx.subscribe(&ingred_type, |this, _, _: &TypeChanged, cx| {
+            this.mark_dirty(cx);
+            cx.emit(Changed {});
+        })
+        .detach();
+        cx.subscribe(&ingred_type, |this, _, _: &TypeSelected, cx| {
+            this.record(cx)
+        })
+        .detach();
+        for input in ingred.read(cx).inputs().map(Entity::clone) {
+            cx.subscribe(&input, |this, _, _: &ContentChanged, cx| {
+                this.mark_dirty(cx);
+                cx.emit(Changed {});
+            })
+            .detach();
+            cx.on_blur(&input.focus_handle(cx), window, |this, _window, cx| {
+                this.record(cx)
+            })
+            .detach();
         }
+
+        self.ingreds.insert(ix, ingred.clone());
+        self.count += 1;
+        self.reindex(ix + 1, cx);
+        self.mark_dirty(cx);
+        ingred
+    }
+
+    /// Insert a row at ix, filled from state if given, and focus it
+    fn insert_row(
+        &mut self,
+        ix: usize,
+        state: Option<&RecipeIngredient>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.record(cx);
+        let ingred = self.insert(ix, window, cx);
+        ingred.update(cx, |ingred, cx| {
+            if let Some(state) = state {
+                ingred.set(state, cx);
+            }
+            ingred.ingred_type.read(cx).focus(window);
+        });
+        self.record(cx);
+        self.scroll_handle
+            .scroll_to_item(ingred.read(cx).id, ScrollStrategy::Top);
         cx.notify();
     }
 
+    fn duplicate_row(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ingred) = self.ingreds.get(ix) {
+            let state = ingred.read(cx).state(cx);
+            self.insert_row(ix + 1, Some(&state), window, cx);
+        }
+    }
+
+    fn insert_above(&mut self, _: &InsertAbove, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self.focused_ingred(window, cx).unwrap_or(0);
+        self.insert_row(ix, None, window, cx);
+    }
+
+    fn insert_below(&mut self, _: &InsertBelow, window: &mut Window, cx: &mut Context<Self>) {
+        let ix = self
+            .focused_ingred(window, cx)
+            .map_or(self.count, |ix| ix + 1);
+        self.insert_row(ix, None, window, cx);
+    }
+
+    fn duplicate(&mut self, _: &Duplicate, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.focused_ingred(window, cx) {
+            self.duplicate_row(ix, window, cx);
+        }
+    }
+
     fn delete(&mut self, _: &Delete, window: &mut Window, cx: &mut Context<Self>) {
         if self.count > 0 {
-            if self.parts(self.count - 1, cx).is_focused(window)
-                || self.percentage(self.count - 1, cx).is_focused(window)
-                || self.ingred_type(self.count - 1, cx).is_focused(window)
+            self.record(cx);
+            if self
+                .focused_cell(window, cx)
+                .is_some_and(|(row, _)| row == self.count - 1)
             {
                 self.focus(&Escape, window, cx);
             }
             self.ingreds.pop();
             self.count -= 1;
+            self.clamp_cell(cx);
+            self.mark_dirty(cx);
+            self.record(cx);
         }
         cx.notify();
     }
@@ -100,36 +322,312 @@ impl Table {
         if self.count > 0 && ix < self.count {
             self.ingreds.remove(ix);
             self.count -= 1;
+            self.reindex(ix, cx);
+            self.clamp_cell(cx);
+            self.mark_dirty(cx);
+        }
+    }
 
-            // update id's so that we can use them for indexed removal and dd deferral
-            self.ingreds[ix..]
-                .iter()
-                .enumerate()
-                .for_each(|(jx, ingred)| {
-                    ingred.update(cx, |ingred, cx| {
-                        ingred.id = jx + ix;
-                        ingred.ingred_type.update(cx, |ingred_type, _cx| {
-                            ingred_type.id = jx + ix;
-                        });
-                    })
-                });
+    /// Move the ingred at `from` to `to`, shifting those in between
+    fn move_ingred(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from == to || from >= self.count || to >= self.count {
+            return;
+        }
+
+        self.record(cx);
+        let ingred = self.ingreds.remove(from);
+        self.ingreds.insert(to, ingred);
+        self.reindex(from.min(to), cx);
+        self.mark_dirty(cx);
+        self.record(cx);
+        if let Some((row, column)) = self.navigating
+            && row == from
+        {
+            self.select_cell(Some((to, column)), cx);
         }
+        self.scroll_handle.scroll_to_item(to, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    // update id's so that we can use them for indexed removal and element ids
+    fn reindex(&mut self, from: usize, cx: &mut Context<Self>) {
+        self.ingreds[from..]
+            .iter()
+            .enumerate()
+            .for_each(|(jx, ingred)| ingred.update(cx, |ingred, cx| ingred.set_id(jx + from, cx)));
+    }
+
+    /// The (row, column) of the field being edited
+    fn focused_cell(&self, window: &mut Window, cx: &Context<Self>) -> Option<(usize, usize)> {
+        self.ingreds.iter().enumerate().find_map(|(row, ingred)| {
+            ingred
+                .read(cx)
+                .focus_handles(cx)
+                .iter()
+                .position(|field| field.is_focused(window))
+                .map(|column| (row, column))
+        })
+    }
+
+    /// The row being edited or, while navigating, the selected one
+    fn focused_ingred(&self, window: &mut Window, cx: &Context<Self>) -> Option<usize> {
+        self.focused_cell(window, cx)
+            .or(self.navigating)
+            .map(|(row, _)| row)
     }
 
     fn remove_key(&mut self, _: &RemoveKey, window: &mut Window, cx: &mut Context<Self>) {
-        for ix in 0..self.count {
-            if self.ingred_type(ix, cx).is_focused(window)
-                || self.parts(ix, cx).is_focused(window)
-                || self.percentage(ix, cx).is_focused(window)
-            {
-                self.remove(ix, cx);
+        if let Some(ix) = self.focused_ingred(window, cx) {
+            self.record(cx);
+            self.remove(ix, cx);
+            self.record(cx);
+            if self.navigating.is_none() {
                 self.focus(&Escape, window, cx);
-                break;
             }
         }
         cx.notify();
     }
 
+    /// Select the cell to navigate from (or stop navigating), marking it on its row
+    fn select_cell(&mut self, cell: Option<(usize, usize)>, cx: &mut Context<Self>) {
+        self.navigating = cell;
+        self.ingreds.iter().enumerate().for_each(|(row, ingred)| {
+            let column = cell.filter(|cell| cell.0 == row).map(|cell| cell.1);
+            ingred.update(cx, |ingred, cx| ingred.select_cell(column, cx));
+        });
+        cx.notify();
+    }
+
+    // keep the selected cell on a row that still exists
+    fn clamp_cell(&mut self, cx: &mut Context<Self>) {
+        if let Some((row, column)) = self.navigating {
+            let cell = self
+                .count
+                .checked_sub(1)
+                .map(|last| (row.min(last), column));
+            self.select_cell(cell, cx);
+        }
+    }
+
+    /// Move the selected cell by rows and columns, stopping at the grid's edges
+    fn step_cell(&mut self, rows: isize, columns: isize, cx: &mut Context<Self>) {
+        let Some((row, column)) = self.navigating else {
+            return;
+        };
+        let row = row
+            .saturating_add_signed(rows)
+            .min(self.count.saturating_sub(1));
+        let column = column.saturating_add_signed(columns).min(TAB_STOPS - 1);
+        self.select_cell(Some((row, column)), cx);
+        self.scroll_handle.scroll_to_item(row, ScrollStrategy::Top);
+    }
+
+    fn cell_up(&mut self, _: &CellUp, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step_cell(-1, 0, cx);
+    }
+
+    fn cell_down(&mut self, _: &CellDown, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step_cell(1, 0, cx);
+    }
+
+    fn cell_left(&mut self, _: &CellLeft, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step_cell(0, -1, cx);
+    }
+
+    fn cell_right(&mut self, _: &CellRight, _window: &mut Window, cx: &mut Context<Self>) {
+        self.step_cell(0, 1, cx);
+    }
+
+    /// Stop navigating and start editing the selected cell (opening it if a dropdown)
+    fn edit_cell(&mut self, _: &EditCell, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((row, column)) = self.navigating else {
+            return;
+        };
+        self.select_cell(None, cx);
+        let Some(ingred) = self.ingreds.get(row).cloned() else {
+            return;
+        };
+        ingred.read(cx).focus_handles(cx)[column].focus(window);
+        if column == 0 {
+            ingred.update(cx, |ingred, cx| {
+                ingred.ingred_type.update(cx, |ingred_type, cx| {
+                    if !ingred_type.show {
+                        ingred_type.toggle(cx);
+                    }
+                })
+            });
+        }
+    }
+
+    fn move_up(&mut self, _: &MoveUp, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.focused_ingred(window, cx).filter(|ix| *ix > 0) {
+            self.move_ingred(ix, ix - 1, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.focused_ingred(window, cx) {
+            self.move_ingred(ix, ix + 1, cx);
+        }
+    }
+
+    /// Parse a pasted recipe into rows, replacing any blank ones, and report lines
+    /// that couldn't be parsed
+    fn paste(&mut self, _: &Paste, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        let recipe = parse_recipe(&text);
+        let unknown = recipe.unknown;
+        self.record(cx);
+
+        if !recipe.ingreds.is_empty() {
+            let blank: Vec<usize> = (0..self.count)
+                .filter(|ix| self.ingreds[*ix].read(cx).is_blank(cx))
+                .collect();
+            blank.iter().rev().for_each(|ix| self.remove(*ix, cx));
+        }
+
+        recipe.ingreds.iter().for_each(|data| {
+            self.push(window, cx)
+                .update(cx, |ingred, cx| ingred.set(&data.into(), cx));
+        });
+
+        if let Some(units) = recipe.units {
+            self.num_drinks_input.update(cx, |input, cx| {
+                input.set_content(units.to_string().into(), cx)
+            });
+        }
+
+        if !unknown.is_empty() {
+            toast(
+                cx,
+                ToastVariant::Error,
+                &format!("Couldn't parse pasted line(s): {}", unknown.join("; ")),
+            );
+        }
+        self.record(cx);
+        cx.notify();
+    }
+
+    /// Snapshot the units and each ingredient's fields
+    pub fn recipe(&self, cx: &App) -> Recipe {
+        Recipe {
+            units: self.num_drinks_input.read(cx).content.to_string(),
+            ingreds: self
+                .ingreds
+                .iter()
+                .map(|ingred| ingred.read(cx).state(cx))
+                .collect(),
+        }
+    }
+
+    /// Name the recipe after its first ingredient's label or type (e.g., "Gin + 2")
+    pub fn label(&self, cx: &App) -> SharedString {
+        match self.ingreds.first().map(|ingred| ingred.read(cx)) {
+            Some(first) if !first.is_blank(cx) => {
+                let name = match first.label_input.read(cx).content.trim() {
+                    "" => first.ingred_type.read(cx).current.insert_spaces(),
+                    label => label.to_string().into(),
+                };
+                match self.count {
+                    1 => name,
+                    count => format!("{name} + {}", count - 1).into(),
+                }
+            }
+            _ => "New Recipe".into(),
+        }
+    }
+
+    /// Replace the units and ingredients with those from a snapshot, starting the undo
+    /// history from it
+    pub fn restore(&mut self, recipe: &Recipe, window: &mut Window, cx: &mut Context<Self>) {
+        self.fill(recipe, window, cx);
+        self.recipe = self.recipe(cx);
+    }
+
+    /// Replace the units and ingredients with those from a snapshot
+    fn fill(&mut self, recipe: &Recipe, window: &mut Window, cx: &mut Context<Self>) {
+        self.ingreds.clear();
+        self.count = 0;
+        self.mark_dirty(cx);
+        recipe.ingreds.iter().for_each(|state: &RecipeIngredient| {
+            self.push(window, cx)
+                .update(cx, |ingred, cx| ingred.set(state, cx));
+        });
+        self.num_drinks_input.update(cx, |input, cx| {
+            input.set_content(recipe.units.clone().into(), cx)
+        });
+        self.clamp_cell(cx);
+        cx.notify();
+    }
+
+    /// Record the last snapshot as an undo step if the table changed since then
+    ///
+    /// Row actions record before (so that edits still pending in a field are their own
+    /// step) and after changing the table, while a field's edits are recorded once it
+    /// loses focus, so that each visit to a field undoes as one step. Within a focused
+    /// field, TextInput's own history undoes first
+    ///
+    /// *Note that changes are ignored while a dropdown is open, as its current type
+    /// is only previewed until selected*
+    fn record(&mut self, cx: &mut Context<Self>) {
+        if self
+            .ingreds
+            .iter()
+            .any(|ingred| ingred.read(cx).ingred_type.read(cx).show)
+        {
+            return;
+        }
+
+        let recipe = self.recipe(cx);
+        if recipe != self.recipe {
+            let prev = std::mem::replace(&mut self.recipe, recipe);
+            self.undo_stack.push(prev);
+            if self.undo_stack.len() > MAX_HISTORY {
+                self.undo_stack.remove(0);
+            }
+            self.redo_stack.clear();
+            cx.emit(Changed {});
+        }
+    }
+
+    fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
+        self.record(cx);
+        if let Some(recipe) = self.undo_stack.pop() {
+            self.redo_stack.push(self.recipe.clone());
+            self.travel(recipe, window, cx);
+        }
+    }
+
+    fn redo(&mut self, _: &Redo, window: &mut Window, cx: &mut Context<Self>) {
+        self.record(cx);
+        if let Some(recipe) = self.redo_stack.pop() {
+            self.undo_stack.push(self.recipe.clone());
+            self.travel(recipe, window, cx);
+        }
+    }
+
+    /// Restore a snapshot from the history without recording it as a new change
+    fn travel(&mut self, recipe: Recipe, window: &mut Window, cx: &mut Context<Self>) {
+        let refocus = self.focus_handle.contains_focused(window, cx);
+        self.fill(&recipe, window, cx);
+        self.recipe = recipe;
+        if refocus {
+            self.focus_handle.focus(window);
+        }
+        cx.emit(Changed {});
+    }
+
+    /// Start fresh with a single blank ingredient
+    fn clear(&mut self, _: &Clear, window: &mut Window, cx: &mut Context<Self>) {
+        self.record(cx);
+        self.fill(&Recipe::default(), window, cx);
+        self.push(window, cx);
+        self.record(cx);
+        self.num_drinks(cx).focus(window);
+    }
+
     pub fn show_num_drinks_cursor(&mut self, cx: &mut Context<Self>) {
         self.num_drinks_input
             .update(cx, |num_drinks, cx| num_drinks.show_cursor(cx));
@@ -141,27 +639,123 @@ impl Table {
             .for_each(|ingred| ingred.update(cx, |ingred, cx| ingred.show_cursor_and_hide_dd(cx)));
     }
 
+    fn tab(&mut self, _: &Tab, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_focus(1, window, cx);
+    }
+
+    fn tab_prev(&mut self, _: &TabPrev, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_focus(-1, window, cx);
+    }
+
+    /// num_drinks_input, then each ingred's fields in the order they're currently laid out
+    fn focus_order(&self, cx: &App) -> FocusOrder {
+        FocusOrder::from_rows(
+            std::iter::once(vec![self.num_drinks_input.focus_handle(cx)]).chain(
+                self.ingreds
+                    .iter()
+                    .map(|ingred| ingred.read(cx).focus_handles(cx).to_vec()),
+            ),
+        )
+    }
+
+    /// Move focus between num_drinks_input and the ingreds' fields, scrolling the target
+    /// into view
+    ///
+    /// *Note that this is needed as rows scrolled out of the list aren't rendered, so the
+    /// window doesn't know about their tab stops. Stepping past either end is left to UI*
+    fn step_focus(&mut self, delta: isize, window: &mut Window, cx: &mut Context<Self>) {
+        let focus_order = self.focus_order(cx);
+        let Some((row, field)) = focus_order.step(delta, window) else {
+            cx.propagate();
+            return;
+        };
+
+        field.focus(window);
+        // row 0 is num_drinks_input, which sits above the list
+        if let Some(ix) = row.checked_sub(1) {
+            self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        }
+        self.show_num_drinks_cursor(cx);
+        self.show_cursor_and_hide_dd(cx);
+        cx.notify();
+    }
+
+    fn render_scrollbar(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let scroll_handle = self.scroll_handle.0.borrow();
+        let list_height = scroll_handle.base_handle.bounds().size.height;
+        let scroll_top = -scroll_handle.base_handle.offset().y;
+        let content_height = scroll_handle
+            .last_item_size
+            .unwrap_or_default()
+            .contents
+            .height;
+
+        if content_height <= list_height {
+            return None;
+        }
+
+        let percentage = scroll_top / (content_height - list_height);
+        let offset_top = ((list_height - SCROLLBAR_THUMB_HEIGHT) * percentage)
+            .clamp(px(0.), (list_height - SCROLLBAR_THUMB_HEIGHT).max(px(0.)));
+
+        Some(
+            div()
+                .id("ingreds_scrollbar")
+                .absolute()
+                .top(offset_top)
+                .right_0()
+                .block_mouse_except_scroll()
+                .h(SCROLLBAR_THUMB_HEIGHT)
+                .w(SCROLLBAR_THUMB_WIDTH)
+                .bg(cx.theme().scrollbar)
+                .hover(|this| this.bg(cx.theme().scrollbar_hover))
+                .active(|this| this.bg(cx.theme().scrollbar))
+                .rounded_lg(),
+        )
+    }
+
     fn ready(&mut self, cx: &mut Context<Self>) -> bool {
         if self.ingreds.is_empty() {
             return false;
         }
 
-        (0..self.count).all(|ix| {
-            let percentage = self.parse_or_zero(&self.percentage(ix, cx).content);
-            let parts = self.parse_or_zero(&self.parts(ix, cx).content);
-            percentage > 0. && (self.count <= 1 || parts > 0.)
-        })
+        self.num_drinks(cx).is_valid()
+            && (0..self.count).all(|ix| {
+                let percentage = self.percentage(ix, cx);
+                let parts = self.parts(ix, cx);
+                percentage.is_valid()
+                    && parts.is_valid()
+                    && self.parse_or_zero(&percentage.content) > 0.
+                    && (self.count <= 1 || self.parse_or_zero(&parts.content) > 0.)
+            })
     }
 
-    fn calc(&mut self, cx: &mut Context<Self>, num_drinks: f32) {
-        let mut ingred_data: Vec<IngredientData> = (0..self.count)
+    fn data(&self, cx: &Context<Self>) -> Vec<IngredientData> {
+        (0..self.count)
             .map(|ix| IngredientData {
                 ingred_type: self.ingred_type(ix, cx).current.clone(),
+                label: self.ingreds[ix]
+                    .read(cx)
+                    .label_input
+                    .read(cx)
+                    .content
+                    .clone(),
+                notes: self.ingreds[ix]
+                    .read(cx)
+                    .notes_input
+                    .read(cx)
+                    .content
+                    .clone(),
                 percentage: self.parse_or_zero(&self.percentage(ix, cx).content),
                 parts: self.parse_or_zero(&self.parts(ix, cx).content),
+                weight: self.parse_or_zero(self.weight(ix, cx)),
                 ..Default::default()
             })
-            .collect();
+            .collect()
+    }
+
+    fn calc(&mut self, cx: &mut Context<Self>, num_drinks: f32) {
+        let mut ingred_data = self.data(cx);
 
         let ingred_data = match calc_weights(&mut ingred_data, num_drinks) {
             Ok(ingred_data) => ingred_data,
@@ -180,7 +774,104 @@ impl Table {
                 // default to 0th ingred as both vecs are nonempty due to ready check
                 ingred.weight(ingred_data.get(ix).unwrap_or(&ingred_data[0]).weight);
             });
+        });
+        self.calculated = Some(ingred_data.clone());
+    }
+
+    fn mark_dirty(&mut self, cx: &mut Context<Self>) {
+        self.dirty = true;
+        cx.notify();
+    }
+
+    /// Recalculate weights, but only if an input changed since they were last calculated
+    fn recalc(&mut self, cx: &mut Context<Self>) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        self.calculated = None;
+        self.num_drinks = self.parse_or_zero(&self.num_drinks(cx).content);
+
+        if self.ready(cx) {
+            self.calc(cx, self.num_drinks);
+        }
+    }
+
+    /// Format the current units and ingredients, or notify the user that there's nothing
+    /// worth exporting yet
+    fn export(&mut self, format: ExportFormat, cx: &mut Context<Self>) -> Option<String> {
+        self.recalc(cx);
+        let Some(data) = &self.calculated else {
+            toast(
+                cx,
+                ToastVariant::Info,
+                "Fill in each ingredient's percentage and parts before exporting",
+            );
+            return None;
+        };
+
+        Some(format.export(self.num_drinks, data))
+    }
+
+    fn copy(&mut self, format: ExportFormat, cx: &mut Context<Self>) {
+        if let Some(content) = self.export(format, cx) {
+            cx.write_to_clipboard(ClipboardItem::new_string(content));
+            toast(
+                cx,
+                ToastVariant::Info,
+                &format!("Copied recipe to the clipboard as {format}"),
+            );
+        }
+    }
+
+    fn save(&mut self, format: ExportFormat, cx: &mut Context<Self>) {
+        let Some(content) = self.export(format, cx) else {
+            return;
+        };
+        let directory = dirs::document_dir()
+            .or(dirs::home_dir())
+            .unwrap_or_default();
+        let path =
+            cx.prompt_for_new_path(&directory, Some(&format!("recipe.{}", format.extension())));
+
+        cx.spawn(async move |_, cx| {
+            if let Ok(Ok(Some(path))) = path.await {
+                cx.update(|cx| match std::fs::write(&path, content) {
+                    Ok(_) => toast(
+                        cx,
+                        ToastVariant::Info,
+                        &format!("Saved recipe to {}", path.display()),
+                    ),
+                    Err(_) => toast(cx, ToastVariant::Error, "Failed to write recipe file"),
+                })
+                .ok();
+            }
         })
+        .detach();
+    }
+
+    fn copy_csv(&mut self, _: &CopyCsv, _window: &mut Window, cx: &mut Context<Self>) {
+        self.copy(ExportFormat::Csv, cx);
+    }
+
+    fn copy_markdown(&mut self, _: &CopyMarkdown, _window: &mut Window, cx: &mut Context<Self>) {
+        self.copy(ExportFormat::Markdown, cx);
+    }
+
+    fn copy_text(&mut self, _: &CopyText, _window: &mut Window, cx: &mut Context<Self>) {
+        self.copy(ExportFormat::Text, cx);
+    }
+
+    fn save_csv(&mut self, _: &SaveCsv, _window: &mut Window, cx: &mut Context<Self>) {
+        self.save(ExportFormat::Csv, cx);
+    }
+
+    fn save_markdown(&mut self, _: &SaveMarkdown, _window: &mut Window, cx: &mut Context<Self>) {
+        self.save(ExportFormat::Markdown, cx);
+    }
+
+    fn save_text(&mut self, _: &SaveText, _window: &mut Window, cx: &mut Context<Self>) {
+        self.save(ExportFormat::Text, cx);
     }
 
     fn num_drinks<'a>(&'a self, cx: &'a Context<Self>) -> &'a TextInput {
@@ -219,38 +910,85 @@ impl Table {
             .read(cx)
     }
 
+    fn weight<'a>(&'a self, ix: usize, cx: &'a Context<Self>) -> &'a SharedString {
+        &self
+            .ingreds
+            .get(ix)
+            .unwrap_or(&self.ingreds[0])
+            .read(cx)
+            .weight
+    }
+
     fn parse_or_zero(&self, content: &SharedString) -> f32 {
         content.trim().parse().unwrap_or(0.)
     }
 
-    fn focus(&mut self, _: &Escape, window: &mut Window, _cx: &mut Context<Self>) {
+    /// Leave editing to navigate from the cell being edited, if any
+    fn focus(&mut self, _: &Escape, window: &mut Window, cx: &mut Context<Self>) {
+        let cell = self.focused_cell(window, cx);
         self.focus_handle.focus(window);
+        self.select_cell(cell, cx);
     }
 }
 
-impl EventEmitter<Add> for Table {}
+/// Emitted whenever an edit changes the table's contents
+pub struct Changed {}
+
+impl EventEmitter<Changed> for Table {}
 
 impl Render for Table {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        // focus num_drinks_input and add ingred on launch
+        // focus num_drinks_input and add ingred on launch (unless a recipe was restored)
         if self.init {
-            self.add(&Add, window, cx);
+            if self.count == 0 {
+                self.push(window, cx);
+                self.recipe = self.recipe(cx);
+            }
             self.num_drinks(cx).focus(window);
             self.init = false;
         }
 
-        self.num_drinks = self.parse_or_zero(&self.num_drinks(cx).content);
+        self.recalc(cx);
 
-        if self.ready(cx) {
-            self.calc(cx, self.num_drinks);
+        // focusing anything else (e.g., clicking a field) ends navigation
+        if self.navigating.is_some() && !self.focus_handle.is_focused(window) {
+            self.select_cell(None, cx);
+        }
+
+        let mut context = KeyContext::default();
+        context.add(CONTEXT);
+        if self.navigating.is_some() {
+            context.add(NAVIGATING);
         }
 
         div()
-            .key_context(CONTEXT)
+            .key_context(context)
             .on_action(cx.listener(Self::focus))
+            .on_action(cx.listener(Self::cell_up))
+            .on_action(cx.listener(Self::cell_down))
+            .on_action(cx.listener(Self::cell_left))
+            .on_action(cx.listener(Self::cell_right))
+            .on_action(cx.listener(Self::edit_cell))
             .on_action(cx.listener(Self::add))
             .on_action(cx.listener(Self::delete))
             .on_action(cx.listener(Self::remove_key))
+            .on_action(cx.listener(Self::insert_above))
+            .on_action(cx.listener(Self::insert_below))
+            .on_action(cx.listener(Self::duplicate))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::clear))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
+            .on_action(cx.listener(Self::copy_csv))
+            .on_action(cx.listener(Self::copy_markdown))
+            .on_action(cx.listener(Self::copy_text))
+            .on_action(cx.listener(Self::save_csv))
+            .on_action(cx.listener(Self::save_markdown))
+            .on_action(cx.listener(Self::save_text))
+            .on_action(cx.listener(Self::paste))
+            .on_action(cx.listener(Self::tab))
+            .on_action(cx.listener(Self::tab_prev))
             .track_focus(&self.focus_handle(cx))
             .flex()
             .flex_col()
@@ -296,7 +1034,6 @@ impl Render for Table {
                 div()
                     .flex()
                     .flex_col()
-                    .flex_1()
                     .p_4()
                     .gap_2()
                     .bg(cx.theme().foreground)
@@ -306,7 +1043,7 @@ impl Render for Table {
                         div()
                             .flex()
                             .flex_row()
-                            .ml_8()
+                            .ml_16()
                             .h_5()
                             .gap_x_4()
                             .overflow_hidden()
@@ -328,11 +1065,26 @@ impl Render for Table {
                     // ingreds
                     .child(
                         div()
+                            .relative()
                             .flex()
                             .flex_col()
+                            .pr_3()
                             .border_t_1()
                             .border_color(cx.theme().background)
-                            .children(self.ingreds.clone()),
+                            .child(
+                                uniform_list(
+                                    "ingreds",
+                                    self.count,
+                                    cx.processor(|this, range: Range<usize>, _window, _cx| {
+                                        range
+                                            .filter_map(|ix| this.ingreds.get(ix).cloned())
+                                            .collect()
+                                    }),
+                                )
+                                .track_scroll(self.scroll_handle.clone())
+                                .h(ROW_HEIGHT * self.count.min(MAX_VISIBLE_ROWS) as f32),
+                            )
+                            .children(self.render_scrollbar(cx)),
                     )
                     // + button
                     .child(
@@ -346,10 +1098,13 @@ impl Render for Table {
                                     }),
                                 ))
                                 .id("add_button")
-                                .tooltip(|_window, cx| {
-                                    cx.new(|cx| {
+                                .tooltip(|window, cx| {
+                                    let keybind = Keymap::keybind(window, &Add, CONTEXT);
+                                    cx.new(|_cx| {
                                         Tooltip::new("Add an Ingredient")
-                                            .keybind(&format!("{}-i", cx.ctrl()))
+                                            .when_some(keybind, |this, keybind| {
+                                                this.keybind(&keybind)
+                                            })
                                     })
                                     .into()
                                 }),
@@ -371,6 +1126,7 @@ mod tests {
 
     use super::*;
     use gpui::{TestAppContext, VisualTestContext};
+    use std::{cell::Cell, rc::Rc};
 
     #[gpui::test]
     fn test_table_remove(cx: &mut TestAppContext) {
@@ -398,7 +1154,7 @@ mod tests {
     }
 
     #[gpui::test]
-    fn test_table_add_when_full(cx: &mut TestAppContext) {
+    fn test_table_add_many(cx: &mut TestAppContext) {
         let (table, cx, ctrl) = setup_table(cx);
         let mut num_ingreds = 0;
 
@@ -406,7 +1162,7 @@ mod tests {
         (0..15).for_each(|_| cx.simulate_keystrokes(&format!("{ctrl}-i")));
         table.update(cx, |table, _cx| num_ingreds = table.ingreds.len());
 
-        assert_eq!(MAX_ITEMS, num_ingreds);
+        assert_eq!(16, num_ingreds);
     }
 
     #[gpui::test]
@@ -424,13 +1180,131 @@ mod tests {
         assert_eq!(0, num_ingreds);
     }
 
+    #[gpui::test]
+    fn test_table_move_down(cx: &mut TestAppContext) {
+        let (table, cx, _ctrl) = setup_table(cx);
+        let mut types: Vec<SharedString> = vec![];
+        let mut ids: Vec<(usize, usize)> = vec![];
+        let mut focused = false;
+
+        table.update_in(cx, |table, window, cx| {
+            table.restore(&recipe(), window, cx);
+            table.percentage(0, cx).focus(window);
+        });
+        cx.simulate_keystrokes("alt-down");
+        table.update_in(cx, |table, window, cx| {
+            types = (0..table.count)
+                .map(|ix| table.ingred_type(ix, cx).current.clone())
+                .collect();
+            ids = table
+                .ingreds
+                .iter()
+                .map(|ingred| {
+                    let ingred = ingred.read(cx);
+                    (ingred.id, ingred.ingred_type.read(cx).id)
+                })
+                .collect();
+            table.tab(&Tab, window, cx);
+            focused = table.parts(1, cx).is_focused(window);
+        });
+
+        assert_eq!(vec![SharedString::from("Wine"), "Gin".into()], types);
+        assert_eq!(vec![(0, 0), (1, 1)], ids);
+        assert!(focused);
+    }
+
+    #[gpui::test]
+    fn test_table_move_up_at_limit(cx: &mut TestAppContext) {
+        let (table, cx, _ctrl) = setup_table(cx);
+        let mut result = Recipe::default();
+
+        table.update_in(cx, |table, window, cx| {
+            table.restore(&recipe(), window, cx);
+            table.ingred_type(0, cx).focus(window);
+        });
+        cx.simulate_keystrokes("alt-up");
+        table.update(cx, |table, cx| result = table.recipe(cx));
+
+        assert_eq!(recipe(), result);
+    }
+
+    #[gpui::test]
+    fn test_table_insert_above(cx: &mut TestAppContext) {
+        let (table, cx, ctrl) = setup_table(cx);
+        let mut result = Recipe::default();
+        let mut ids: Vec<usize> = vec![];
+        let mut focused = false;
+
+        table.update_in(cx, |table, window, cx| {
+            table.restore(&recipe(), window, cx);
+            table.percentage(1, cx).focus(window);
+        });
+        cx.simulate_keystrokes(&format!("{ctrl}-shift-enter"));
+        table.update_in(cx, |table, window, cx| {
+            result = table.recipe(cx);
+            ids = table
+                .ingreds
+                .iter()
+                .map(|ingred| ingred.read(cx).id)
+                .collect();
+            focused = table.ingred_type(1, cx).is_focused(window);
+        });
+
+        assert_eq!(3, result.ingreds.len());
+        assert_eq!("Gin", result.ingreds[0].ingred_type);
+        assert_eq!("", result.ingreds[1].percentage);
+        assert_eq!("Wine", result.ingreds[2].ingred_type);
+        assert_eq!(vec![0, 1, 2], ids);
+        assert!(focused);
+    }
+
+    #[gpui::test]
+    fn test_table_duplicate(cx: &mut TestAppContext) {
+        let (table, cx, ctrl) = setup_table(cx);
+        let mut result = Recipe::default();
+
+        table.update_in(cx, |table, window, cx| {
+            table.restore(&recipe(), window, cx);
+            table.parts(0, cx).focus(window);
+        });
+        cx.simulate_keystrokes(&format!("{ctrl}-shift-d"));
+        table.update(cx, |table, cx| result = table.recipe(cx));
+
+        let gin = recipe().ingreds[0].clone();
+        assert_eq!(
+            vec![gin.clone(), gin, recipe().ingreds[1].clone()],
+            result.ingreds
+        );
+    }
+
+    #[gpui::test]
+    fn test_table_insert_below_from_row(cx: &mut TestAppContext) {
+        let (table, cx, _ctrl) = setup_table(cx);
+        let mut types: Vec<SharedString> = vec![];
+
+        table.update_in(cx, |table, window, cx| table.restore(&recipe(), window, cx));
+        table.update(cx, |table, cx| {
+            table.ingreds[0].update(cx, |_ingred, cx| cx.emit(InsertBelow {}))
+        });
+        table.update(cx, |table, cx| {
+            types = (0..table.count)
+                .map(|ix| table.ingred_type(ix, cx).current.clone())
+                .collect();
+        });
+
+        assert_eq!(
+            vec![SharedString::from("Gin"), "Whiskey".into(), "Wine".into()],
+            types
+        );
+    }
+
     #[gpui::test]
     fn test_table_calc_single_ingred(cx: &mut TestAppContext) {
         let (ui, cx, _ctrl) = setup_ui_and_table(cx);
         let mut weight = SharedString::from("");
 
         cx.focus(&ui);
-        cx.simulate_keystrokes("tab 2 tab tab 4 0");
+        cx.simulate_keystrokes("tab 2 tab tab tab 4 0");
         ui.update(cx, |ui, cx| {
             ui.table.update(cx, |table, cx| {
                 weight = table.ingreds[0].read(cx).weight.clone();
@@ -440,14 +1314,113 @@ mod tests {
         assert_eq!(SharedString::from("84.6"), weight);
     }
 
+    #[gpui::test]
+    fn test_table_reject_non_numeric(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui_and_table(cx);
+        let mut percentage = SharedString::from("");
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("tab 2 tab tab tab a b c 4 0");
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                percentage = table.percentage(0, cx).content.clone();
+            });
+        });
+
+        assert_eq!(SharedString::from("40"), percentage);
+    }
+
+    #[gpui::test]
+    fn test_table_not_ready_when_out_of_range(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui_and_table(cx);
+        let mut result = (true, true);
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("tab 2 tab tab tab 1 5 0");
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                result = (table.percentage(0, cx).is_valid(), table.ready(cx));
+            });
+        });
+
+        assert_eq!((false, false), result);
+    }
+
+    #[gpui::test]
+    fn test_table_step_numeric(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui_and_table(cx);
+        let mut result = (SharedString::from(""), SharedString::from(""));
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("tab 1 . 5 up tab tab tab 4 0 up shift-up shift-up");
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                result = (
+                    table.num_drinks(cx).content.clone(),
+                    table.percentage(0, cx).content.clone(),
+                );
+            });
+        });
+
+        assert_eq!(
+            (SharedString::from("2.5"), SharedString::from("41.2")),
+            result
+        );
+    }
+
+    #[gpui::test]
+    fn test_table_step_numeric_at_limit(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui_and_table(cx);
+        let mut result = (SharedString::from(""), SharedString::from(""));
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("tab shift-down tab tab tab 9 9 . 5 up");
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                result = (
+                    table.num_drinks(cx).content.clone(),
+                    table.percentage(0, cx).content.clone(),
+                );
+            });
+        });
+
+        assert_eq!((SharedString::from("0"), SharedString::from("100")), result);
+    }
+
+    #[gpui::test]
+    fn test_table_recalc_only_on_change(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui_and_table(cx);
+        let mut weight = vec![];
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("tab 2 tab tab tab 4 0");
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                table.ingreds[0].update(cx, |ingred, _cx| ingred.weight(0.));
+                cx.notify();
+            });
+        });
+        for keystrokes in ["left right", "backspace"] {
+            cx.simulate_keystrokes(keystrokes);
+            ui.update(cx, |ui, cx| {
+                weight.push(ui.table.read(cx).ingreds[0].read(cx).weight.clone());
+            });
+        }
+
+        assert_eq!(
+            vec![SharedString::from("0"), SharedString::from("1225.1")],
+            weight
+        );
+    }
+
     #[gpui::test]
     fn test_table_calc_multiple_ingreds(cx: &mut TestAppContext) {
         let (ui, cx, ctrl) = setup_ui_and_table(cx);
         let mut weight: Vec<SharedString> = vec!["".into(), "".into()];
 
         cx.focus(&ui);
-        cx.simulate_keystrokes(&format!("tab {ctrl}-i 2 tab tab 4 0 tab 1 . 5"));
-        cx.simulate_keystrokes("tab enter k k k k enter tab 1 6 . 5 tab 1");
+        cx.simulate_keystrokes(&format!("tab {ctrl}-i 2 tab tab tab 4 0 tab 1 . 5"));
+        cx.simulate_keystrokes("tab tab enter k k k k enter tab tab 1 6 . 5 tab 1");
         ui.update(cx, |ui, cx| {
             ui.table.update(cx, |table, cx| {
                 weight[0] = table.ingreds[0].read(cx).weight.clone();
@@ -459,6 +1432,223 @@ mod tests {
         assert_eq!(SharedString::from("46.9"), weight[1]);
     }
 
+    #[gpui::test]
+    fn test_table_copy_markdown(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab 2 tab tab tab 4 0 {ctrl}-e"));
+        let content = cx
+            .read_from_clipboard()
+            .and_then(|item| item.text())
+            .unwrap_or_default();
+
+        assert_eq!(
+            "**Units:** 2\n\n| Ingredient | Percentage | Parts | Weight (g) |\n\
+             | --- | --- | --- | --- |\n| Whiskey | 40% | 0 | 84.6g |\n",
+            content
+        );
+    }
+
+    #[gpui::test]
+    fn test_table_paste_recipe(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+        let mut rows: Vec<(SharedString, SharedString, SharedString)> = vec![];
+
+        cx.write_to_clipboard(ClipboardItem::new_string(
+            "Gin 40% 2\nCampari 25% 1\nVodka 35 1".into(),
+        ));
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab 2 {ctrl}-v"));
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                rows = table
+                    .ingreds
+                    .iter()
+                    .map(|ingred| {
+                        let ingred = ingred.read(cx);
+                        (
+                            ingred.ingred_type.read(cx).current.clone(),
+                            ingred.percentage_input.read(cx).content.clone(),
+                            ingred.parts_input.read(cx).content.clone(),
+                        )
+                    })
+                    .collect();
+            });
+        });
+
+        assert_eq!(
+            vec![
+                ("Gin".into(), "40".into(), "2".into()),
+                ("Vodka".into(), "35".into(), "1".into())
+            ],
+            rows
+        );
+    }
+
+    #[gpui::test]
+    fn test_table_restore(cx: &mut TestAppContext) {
+        let (table, cx, _ctrl) = setup_table(cx);
+        let recipe = recipe();
+        let mut result = Recipe::default();
+
+        table.update_in(cx, |table, window, cx| table.restore(&recipe, window, cx));
+        cx.run_until_parked();
+        table.update(cx, |table, cx| result = table.recipe(cx));
+
+        assert_eq!(recipe, result);
+    }
+
+    #[gpui::test]
+    fn test_table_clear(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+        let mut result = Recipe::default();
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab 2 {ctrl}-i tab tab tab 4 0 {ctrl}-shift-r"));
+        ui.update(cx, |ui, cx| result = ui.table.read(cx).recipe(cx));
+
+        assert_eq!("", result.units);
+        assert_eq!(1, result.ingreds.len());
+        assert_eq!("", result.ingreds[0].percentage);
+    }
+
+    #[gpui::test]
+    fn test_table_undo_remove(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+        let mut result = Recipe::default();
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab tab tab tab 4 0 {ctrl}-r {ctrl}-z"));
+        ui.update(cx, |ui, cx| result = ui.table.read(cx).recipe(cx));
+
+        assert_eq!(1, result.ingreds.len());
+        assert_eq!("40", result.ingreds[0].percentage);
+    }
+
+    #[gpui::test]
+    fn test_table_undo_field_as_one_step(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+        let mut result = vec![];
+
+        // blur listeners only fire in an active window
+        cx.update(|window, _| window.activate_window());
+        cx.run_until_parked();
+        cx.focus(&ui);
+        // each field is left in its own frame, as focus listeners run on draw
+        cx.simulate_keystrokes("tab tab tab tab 4 0 tab");
+        cx.simulate_keystrokes("2 escape");
+        (0..2).for_each(|_| {
+            cx.simulate_keystrokes(&format!("{ctrl}-z"));
+            ui.update(cx, |ui, cx| {
+                let recipe = ui.table.read(cx).recipe(cx);
+                result.push((
+                    recipe.ingreds[0].percentage.clone(),
+                    recipe.ingreds[0].parts.clone(),
+                ));
+            });
+        });
+
+        assert_eq!(
+            vec![("40".into(), "".into()), ("".into(), "".into())],
+            result
+        );
+    }
+
+    #[gpui::test]
+    fn test_table_undo_from_field(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+        let mut result = Recipe::default();
+
+        // the field's own history undoes the typing, then the table's undoes the add
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("{ctrl}-i tab tab tab tab 4 {ctrl}-z {ctrl}-z"));
+        ui.update(cx, |ui, cx| result = ui.table.read(cx).recipe(cx));
+
+        assert_eq!(1, result.ingreds.len());
+        assert_eq!("", result.ingreds[0].percentage);
+    }
+
+    #[gpui::test]
+    fn test_table_redo_add(cx: &mut TestAppContext) {
+        let (table, cx, ctrl) = setup_table(cx);
+        let mut num_ingreds = 0;
+
+        cx.focus(&table);
+        cx.simulate_keystrokes(&format!(
+            "{ctrl}-i {ctrl}-i {ctrl}-z {ctrl}-z {ctrl}-shift-z"
+        ));
+        table.update(cx, |table, _cx| num_ingreds = table.ingreds.len());
+
+        assert_eq!(2, num_ingreds);
+    }
+
+    #[gpui::test]
+    fn test_table_changed_on_edit_only(cx: &mut TestAppContext) {
+        let (table, cx, _ctrl) = setup_table(cx);
+        let changed = Rc::new(Cell::new(0));
+
+        table.update_in(cx, |table, window, cx| {
+            let changed = changed.clone();
+            cx.subscribe(&cx.entity(), move |_, _, _: &Changed, _| {
+                changed.set(changed.get() + 1)
+            })
+            .detach();
+            table.percentage(0, cx).focus(window);
+        });
+        cx.simulate_keystrokes("4");
+        // rerendering (e.g., for a cursor blink) isn't an edit
+        (0..3).for_each(|_| {
+            table.update(cx, |_table, cx| cx.notify());
+            cx.run_until_parked();
+        });
+
+        assert_eq!(1, changed.get());
+    }
+
+    #[gpui::test]
+    fn test_table_undo_when_empty(cx: &mut TestAppContext) {
+        let (table, cx, ctrl) = setup_table(cx);
+        let mut num_ingreds = 0;
+
+        cx.focus(&table);
+        cx.simulate_keystrokes(&format!("{ctrl}-z"));
+        table.update(cx, |table, _cx| num_ingreds = table.ingreds.len());
+
+        assert_eq!(1, num_ingreds);
+    }
+
+    #[gpui::test]
+    fn test_table_label(cx: &mut TestAppContext) {
+        let (table, cx, ctrl) = setup_table(cx);
+        let mut labels: Vec<SharedString> = vec![];
+
+        cx.focus(&table);
+        table.update_in(cx, |table, window, cx| {
+            labels.push(table.label(cx));
+            table.percentage(0, cx).focus(window);
+        });
+        cx.simulate_keystrokes("4 0");
+        cx.simulate_keystrokes(&format!("{ctrl}-i {ctrl}-i"));
+        table.update(cx, |table, cx| labels.push(table.label(cx)));
+
+        assert_eq!(
+            vec![SharedString::from("New Recipe"), "Whiskey + 2".into()],
+            labels
+        );
+    }
+
+    #[gpui::test]
+    fn test_table_label_from_ingred_label(cx: &mut TestAppContext) {
+        let (table, cx, _ctrl) = setup_table(cx);
+        let mut label = SharedString::from("");
+
+        table.update_in(cx, |table, window, cx| table.restore(&recipe(), window, cx));
+        table.update(cx, |table, cx| label = table.label(cx));
+
+        assert_eq!(SharedString::from("Fords + 1"), label);
+    }
+
     #[gpui::test]
     fn test_table_not_ready_when_empty(cx: &mut TestAppContext) {
         let (table, cx, ctrl) = setup_table(cx);
@@ -468,7 +1658,44 @@ mod tests {
         cx.simulate_keystrokes(&format!("{ctrl}-d"));
         table.update(cx, |table, cx| ready = table.ready(cx));
 
-        assert_eq!(false, ready);
+        assert!(!ready);
+    }
+
+    #[gpui::test]
+    fn test_table_navigate_and_edit_cell(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+        let mut result = (SharedString::from(""), Some((0, 0)));
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes(&format!("tab {ctrl}-i tab tab tab 4 0 escape"));
+        cx.simulate_keystrokes("j l enter 2");
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                result = (table.parts(1, cx).content.clone(), table.navigating);
+            });
+        });
+
+        assert_eq!((SharedString::from("2"), None), result);
+    }
+
+    #[gpui::test]
+    fn test_table_navigate_at_edge(cx: &mut TestAppContext) {
+        let (ui, cx, _ctrl) = setup_ui_and_table(cx);
+        let mut result = (Some((1, 1)), false);
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("tab tab tab escape k h h up left");
+        ui.update(cx, |ui, cx| {
+            ui.table
+                .update(cx, |table, _cx| result.0 = table.navigating);
+        });
+        cx.simulate_keystrokes("enter");
+        ui.update(cx, |ui, cx| {
+            ui.table
+                .update(cx, |table, cx| result.1 = table.ingred_type(0, cx).show);
+        });
+
+        assert_eq!((Some((0, 0)), true), result);
     }
 
     #[gpui::test]
@@ -478,7 +1705,7 @@ mod tests {
 
         cx.focus(&ui);
         cx.simulate_keystrokes(&format!("tab tab {ctrl}-i"));
-        (0..3).for_each(|_| cx.simulate_keystrokes(&format!("tab")));
+        (0..TAB_STOPS).for_each(|_| cx.simulate_keystrokes("tab"));
         ui.update_in(cx, |ui, window, cx| {
             ui.table.update(cx, |table, cx| {
                 ingred_focused = table.ingreds[1]
@@ -489,7 +1716,47 @@ mod tests {
             });
         });
 
-        assert_eq!(true, ingred_focused);
+        assert!(ingred_focused);
+    }
+
+    #[gpui::test]
+    fn test_table_focus_offscreen_ingred(cx: &mut TestAppContext) {
+        let (ui, cx, ctrl) = setup_ui_and_table(cx);
+        let mut percentage = SharedString::from("");
+
+        cx.focus(&ui);
+        cx.simulate_keystrokes("tab");
+        (0..19).for_each(|_| cx.simulate_keystrokes(&format!("{ctrl}-i")));
+        (0..TAB_STOPS * 19 + 3).for_each(|_| cx.simulate_keystrokes("tab"));
+        cx.simulate_keystrokes("4 0");
+        ui.update(cx, |ui, cx| {
+            ui.table.update(cx, |table, cx| {
+                percentage = table.percentage(19, cx).content.clone();
+            });
+        });
+
+        assert_eq!(SharedString::from("40"), percentage);
+    }
+
+    fn recipe() -> Recipe {
+        Recipe {
+            units: "2".into(),
+            ingreds: vec![
+                RecipeIngredient {
+                    ingred_type: "Gin".into(),
+                    label: "Fords".into(),
+                    percentage: "40".into(),
+                    parts: "2".into(),
+                    notes: "chilled".into(),
+                },
+                RecipeIngredient {
+                    ingred_type: "Wine".into(),
+                    percentage: "12".into(),
+                    parts: "1".into(),
+                    ..Default::default()
+                },
+            ],
+        }
     }
 
     fn setup_ui_and_table(
@@ -497,7 +1764,10 @@ mod tests {
     ) -> (Entity<UI>, &mut VisualTestContext, SharedString) {
         let (ui, cx, ctrl) = setup_ui(cx);
         ui.update_in(cx, |ui, window, cx| {
-            ui.table = cx.new(|cx| Table::new(window, cx))
+            ui.table = cx.new(|cx| Table::new(window, cx));
+            // the UI has to rerender to show the swapped table (binding the table's keys on
+            // every new Table used to refresh all windows, which hid this)
+            cx.notify();
         });
 
         (ui, cx, ctrl)
@@ -513,7 +1783,7 @@ mod tests {
             ctrl = cx.ctrl();
         });
 
-        let (table, cx) = cx.add_window_view(|window, cx| Table::new(window, cx));
+        let (table, cx) = cx.add_window_view(Table::new);
         (table, cx, ctrl)
     }
 }
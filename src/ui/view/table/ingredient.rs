@@ -4,20 +4,31 @@
 // Adapted from: https://github.com/zed-industries/zed/blob/main/crates/gpui/examples/data_table.rs
 
 use crate::ui::{
-    ActiveCtrl,
     comp::{
-        button::icon_button,
+        button::{icon_button, text_button},
         dropdown::Dropdown,
         icon::{Icon, IconSize, IconVariant},
-        input::text_input::TextInput,
+        input::text_input::{Numeric, TextInput},
         tooltip::Tooltip,
     },
-    util::theme::ActiveTheme,
+    util::keymap::Keymap,
+    util::{session::RecipeIngredient, str::Spaceable, theme::ActiveTheme},
+    view::table::data_table::{
+        CONTEXT, Duplicate, InsertAbove, InsertBelow, MoveDown, MoveUp, RemoveKey,
+    },
+};
+use gpui::{
+    Action, App, Entity, EventEmitter, FocusHandle, Focusable, MouseButton, MouseDownEvent, Pixels,
+    Point, SharedString, Window, anchored, deferred, div, prelude::*, px,
 };
-use gpui::{Entity, EventEmitter, Pixels, SharedString, Window, div, prelude::*, px};
 
-pub const FIELDS: [(&str, &str, f32); 4] = [
+pub const FIELDS: [(&str, &str, f32); 6] = [
     ("ingredient", "Type of ingredient (e.g., Whiskey)", 158.),
+    (
+        "label",
+        "Optional name or brand of the ingredient (e.g., Rittenhouse)",
+        132.,
+    ),
     (
         "percentage",
         "Percentage of alcohol in the ingredient",
@@ -33,47 +44,169 @@ pub const FIELDS: [(&str, &str, f32); 4] = [
         "Calculated weight (in g) of this ingredient to pour in the drink",
         72.,
     ),
+    ("notes", "Optional notes on this ingredient", 132.),
 ];
 
+// type, label, percentage, parts, and notes
+pub const TAB_STOPS: usize = 5;
+
+// FIELDS that can be edited, in tab order (which is also their column when navigating)
+const CELLS: [&str; TAB_STOPS] = ["ingredient", "label", "percentage", "parts", "notes"];
+
+// fixed so that the table's list can size itself without measuring rows
+pub const ROW_HEIGHT: Pixels = px(47.);
+
+type MenuAction = fn(&mut Ingredient, &mut Context<Ingredient>);
+
 pub struct Ingredient {
     pub ingred_type: Entity<Dropdown>,
+    pub label_input: Entity<TextInput>,
     pub percentage_input: Entity<TextInput>,
     pub parts_input: Entity<TextInput>,
+    pub notes_input: Entity<TextInput>,
     pub weight: SharedString,
     pub id: usize,
+    menu: Option<Point<Pixels>>,
+    // column of the cell selected while navigating the table
+    selected_cell: Option<usize>,
 }
 
 impl Ingredient {
     pub fn new(id: usize, window: &mut Window, cx: &mut Context<Self>) -> Self {
-        // we have 3 items per ingred and tab_index 1 is num_drinks_input,
-        // so multiply by 3 and offset by two (UI itself is tab_index 0)
         Self {
-            ingred_type: cx.new(|cx| Dropdown::new(id, cx, id as isize * 3 + 2)),
-            percentage_input: cx
-                .new(|cx| TextInput::new(window, cx, "Type here...".into(), id as isize * 3 + 3)),
-            parts_input: cx
-                .new(|cx| TextInput::new(window, cx, "Type here...".into(), id as isize * 3 + 4)),
+            ingred_type: cx.new(|cx| Dropdown::new(id, cx)),
+            label_input: cx.new(|cx| TextInput::new(window, cx, "Optional".into())),
+            percentage_input: cx.new(|cx| {
+                TextInput::new(window, cx, "Type here...".into())
+                    .numeric(Numeric::default().max(100.))
+            }),
+            parts_input: cx.new(|cx| {
+                TextInput::new(window, cx, "Type here...".into()).numeric(Numeric::default())
+            }),
+            notes_input: cx.new(|cx| TextInput::new(window, cx, "Optional".into())),
             weight: "0".into(),
             id,
+            menu: None,
+            selected_cell: None,
         }
     }
 
-    fn render_cell(&self, key: &str, width: Pixels) -> impl IntoElement {
-        div().w(width).child(match key {
-            "ingredient" => div().id("").child(self.ingred_type.clone()),
-            "percentage" => div().id("").child(self.percentage_input.clone()),
-            "parts" => div().id("").child(self.parts_input.clone()),
-            "weight" => {
-                let display_weight = self.weight.to_string() + "g";
-                div()
-                    .w(width) // needs to be set again to inform truncate() of width
-                    .truncate()
-                    .child(display_weight.clone())
-                    .id(format!("{}-weight", self.id).into_element())
-                    .tooltip(move |_window, cx| cx.new(|_cx| Tooltip::new(&display_weight)).into())
-            }
-            _ => div().id("").child("--"),
-        })
+    /// Update this ingredient's position, keeping its dropdown's id in line with it
+    ///
+    /// *Note that tab order isn't tied to ids, as Table derives it from the rows' layout*
+    pub fn set_id(&mut self, id: usize, cx: &mut Context<Self>) {
+        self.id = id;
+        self.ingred_type
+            .update(cx, |ingred_type, _cx| ingred_type.id = id);
+    }
+
+    pub fn select_cell(&mut self, column: Option<usize>, cx: &mut Context<Self>) {
+        if self.selected_cell != column {
+            self.selected_cell = column;
+            cx.notify();
+        }
+    }
+
+    fn render_cell(&self, key: &str, width: Pixels, cx: &App) -> impl IntoElement {
+        let selected = self
+            .selected_cell
+            .is_some_and(|column| CELLS[column] == key);
+
+        div()
+            .relative()
+            .w(width)
+            .child(match key {
+                "ingredient" => div().id("").child(self.ingred_type.clone()),
+                "label" => div().id("").child(self.label_input.clone()),
+                "notes" => div().id("").child(self.notes_input.clone()),
+                "percentage" => div().id("").child(self.percentage_input.clone()),
+                "parts" => div().id("").child(self.parts_input.clone()),
+                "weight" => {
+                    let display_weight = self.weight.to_string() + "g";
+                    div()
+                        .w(width) // needs to be set again to inform truncate() of width
+                        .truncate()
+                        .child(display_weight.clone())
+                        .id(format!("{}-weight", self.id).into_element())
+                        .tooltip(move |_window, cx| {
+                            cx.new(|_cx| Tooltip::new(&display_weight)).into()
+                        })
+                }
+                _ => div().id("").child("--"),
+            })
+            .when(selected, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full()
+                        .rounded_md()
+                        .border_2()
+                        .border_color(cx.theme().cursor),
+                )
+            })
+    }
+
+    /// Fill this ingredient's fields
+    pub fn set(&mut self, state: &RecipeIngredient, cx: &mut Context<Self>) {
+        self.ingred_type.update(cx, |ingred_type, cx| {
+            ingred_type.set(state.ingred_type.clone().into(), cx)
+        });
+        [
+            (&self.label_input, &state.label),
+            (&self.percentage_input, &state.percentage),
+            (&self.parts_input, &state.parts),
+            (&self.notes_input, &state.notes),
+        ]
+        .iter()
+        .for_each(|(input, content)| {
+            input.update(cx, |input, cx| {
+                input.set_content((*content).clone().into(), cx)
+            })
+        });
+    }
+
+    pub fn state(&self, cx: &App) -> RecipeIngredient {
+        RecipeIngredient {
+            ingred_type: self.ingred_type.read(cx).current.to_string(),
+            label: self.label_input.read(cx).content.to_string(),
+            percentage: self.percentage_input.read(cx).content.to_string(),
+            parts: self.parts_input.read(cx).content.to_string(),
+            notes: self.notes_input.read(cx).content.to_string(),
+        }
+    }
+
+    /// This ingredient's text fields in tab order
+    pub fn inputs(&self) -> [&Entity<TextInput>; 4] {
+        [
+            &self.label_input,
+            &self.percentage_input,
+            &self.parts_input,
+            &self.notes_input,
+        ]
+    }
+
+    /// This ingredient's fields in tab order
+    pub fn focus_handles(&self, cx: &App) -> [FocusHandle; TAB_STOPS] {
+        [
+            self.ingred_type.focus_handle(cx),
+            self.label_input.focus_handle(cx),
+            self.percentage_input.focus_handle(cx),
+            self.parts_input.focus_handle(cx),
+            self.notes_input.focus_handle(cx),
+        ]
+    }
+
+    pub fn is_blank(&self, cx: &App) -> bool {
+        [
+            &self.label_input,
+            &self.percentage_input,
+            &self.parts_input,
+            &self.notes_input,
+        ]
+        .iter()
+        .all(|input| input.read(cx).content.trim().is_empty())
     }
 
     pub fn weight(&mut self, weight: f32) {
@@ -83,30 +216,150 @@ impl Ingredient {
     pub fn show_cursor_and_hide_dd(&mut self, cx: &mut Context<Self>) {
         self.ingred_type
             .update(cx, |ingred_type, cx| ingred_type.hide(cx));
-        self.percentage_input
-            .update(cx, |percentage, cx| percentage.show_cursor(cx));
-        self.parts_input
-            .update(cx, |parts, cx| parts.show_cursor(cx));
+        [
+            &self.label_input,
+            &self.percentage_input,
+            &self.parts_input,
+            &self.notes_input,
+        ]
+        .iter()
+        .for_each(|input| input.update(cx, |input, cx| input.show_cursor(cx)));
     }
 
     fn remove(&mut self, cx: &mut Context<Self>) {
         cx.emit(Remove {});
     }
+
+    fn show_menu(&mut self, event: &MouseDownEvent, cx: &mut Context<Self>) {
+        self.menu = Some(event.position);
+        cx.notify();
+    }
+
+    fn hide_menu(&mut self, cx: &mut Context<Self>) {
+        self.menu = None;
+        cx.notify();
+    }
+
+    /// Right-click menu for inserting, duplicating, and removing this row
+    fn render_menu(
+        &self,
+        position: Point<Pixels>,
+        window: &Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let keybind = |action: &dyn Action| Keymap::keybind(window, action, CONTEXT);
+        let items: [(&str, Option<SharedString>, MenuAction); 4] = [
+            ("Insert Above", keybind(&InsertAbove), |_, cx| {
+                cx.emit(InsertAbove {})
+            }),
+            ("Insert Below", keybind(&InsertBelow), |_, cx| {
+                cx.emit(InsertBelow {})
+            }),
+            ("Duplicate", keybind(&Duplicate), |_, cx| {
+                cx.emit(Duplicate {})
+            }),
+            ("Remove", keybind(&RemoveKey), |this, cx| this.remove(cx)),
+        ];
+
+        deferred(
+            anchored().position(position).snap_to_window().child(
+                div()
+                    .id(format!("row_menu_{}", self.id).into_element())
+                    .flex()
+                    .flex_col()
+                    .w(px(220.))
+                    .p_1()
+                    .rounded_md()
+                    .text_sm()
+                    .bg(cx.theme().field)
+                    .text_color(cx.theme().field_text)
+                    .children(items.into_iter().enumerate().map(
+                        |(ix, (label, keybind, on_click))| {
+                            div()
+                                .flex()
+                                .flex_row()
+                                .justify_between()
+                                .rounded_md()
+                                .px_1()
+                                .hover(|this| this.bg(cx.theme().background))
+                                .child(text_button(
+                                    &format!("row_menu_item_{ix}"),
+                                    label.into(),
+                                    cx.listener(move |this, _, _window, cx| {
+                                        this.hide_menu(cx);
+                                        on_click(this, cx);
+                                    }),
+                                ))
+                                .child(div().text_color(cx.theme().subtext).children(keybind))
+                        },
+                    ))
+                    .on_mouse_down_out(cx.listener(|this, _, _window, cx| this.hide_menu(cx))),
+            ),
+        )
+        .with_priority(1)
+    }
+
+    fn drop(&mut self, dragged: &DraggedIngredient, cx: &mut Context<Self>) {
+        cx.emit(Move {
+            from: dragged.id,
+            to: self.id,
+        });
+    }
 }
 
 impl Render for Ingredient {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let id = self.id;
+        let dragged = DraggedIngredient {
+            id,
+            label: self.ingred_type.read(cx).current.insert_spaces(),
+        };
 
         div()
             .flex()
             .flex_row()
             .border_b_1()
             .border_color(cx.theme().background)
-            .py_1()
+            .h(ROW_HEIGHT)
             .items_center()
             .justify_center()
             .gap_x_4()
+            .drag_over::<DraggedIngredient>(|style, _, _window, cx| {
+                style.border_color(cx.theme().cursor)
+            })
+            .on_drop(
+                cx.listener(|this, dragged: &DraggedIngredient, _window, cx| {
+                    this.drop(dragged, cx)
+                }),
+            )
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(|this, event, _window, cx| this.show_menu(event, cx)),
+            )
+            .child(
+                div()
+                    .flex()
+                    .size_4()
+                    .cursor_grab()
+                    .child(
+                        Icon::new(cx, IconVariant::Grip, IconSize::Small).color(cx.theme().subtext),
+                    )
+                    .id(format!("drag_handle_{id}").into_element())
+                    .on_drag(dragged, |dragged, _offset, _window, cx| {
+                        cx.new(|_cx| dragged.clone())
+                    })
+                    .tooltip(|window, cx| {
+                        let keybinds: Vec<SharedString> = [&MoveUp as &dyn Action, &MoveDown]
+                            .into_iter()
+                            .filter_map(|action| Keymap::keybind(window, action, CONTEXT))
+                            .collect();
+                        let text = match keybinds.is_empty() {
+                            true => "Drag to reorder".to_string(),
+                            false => format!("Drag to reorder ({})", keybinds.join(", ")),
+                        };
+                        cx.new(|_cx| Tooltip::new(&text)).into()
+                    }),
+            )
             .child(
                 div()
                     .flex()
@@ -116,25 +369,63 @@ impl Render for Ingredient {
                         cx.listener(move |this, _, _window, cx| this.remove(cx)),
                     ))
                     .id(format!("remove_button_{id}").into_element())
-                    .tooltip(|_window, cx| {
-                        cx.new(|cx| {
+                    .tooltip(|window, cx| {
+                        let keybind = Keymap::keybind(window, &RemoveKey, CONTEXT);
+                        cx.new(|_cx| {
                             Tooltip::new("Remove this Ingredient")
-                                .keybind(&format!("{}-r", cx.ctrl()))
+                                .when_some(keybind, |this, keybind| this.keybind(&keybind))
                         })
                         .into()
                     }),
             )
-            .children(FIELDS.map(|(key, _, width)| self.render_cell(key, px(width))))
+            .children(FIELDS.map(|(key, _, width)| self.render_cell(key, px(width), cx)))
+            .when_some(self.menu, |this, position| {
+                this.child(self.render_menu(position, window, cx))
+            })
     }
 }
 
 pub struct Remove {}
 
+/// Emitted when a row is dropped onto this one, moving it to this row's position
+pub struct Move {
+    pub from: usize,
+    pub to: usize,
+}
+
 impl EventEmitter<Remove> for Ingredient {}
+impl EventEmitter<Move> for Ingredient {}
+impl EventEmitter<InsertAbove> for Ingredient {}
+impl EventEmitter<InsertBelow> for Ingredient {}
+impl EventEmitter<Duplicate> for Ingredient {}
+
+/// The row being dragged, which also renders as its drag preview
+#[derive(Clone)]
+pub struct DraggedIngredient {
+    pub id: usize,
+    pub label: SharedString,
+}
+
+impl Render for DraggedIngredient {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .text_sm()
+            .bg(cx.theme().field)
+            .text_color(cx.theme().field_text)
+            .border_1()
+            .border_color(cx.theme().cursor)
+            .child(self.label.clone())
+    }
+}
 
 #[derive(Clone)]
 pub struct IngredientData {
     pub ingred_type: SharedString,
+    pub label: SharedString,
+    pub notes: SharedString,
     pub percentage: f32,
     pub parts: f32,
     pub density: f32,
@@ -147,6 +438,8 @@ impl Default for IngredientData {
     fn default() -> Self {
         Self {
             ingred_type: "".into(),
+            label: "".into(),
+            notes: "".into(),
             percentage: 0.,
             parts: 0.,
             density: 0.,